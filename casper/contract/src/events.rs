@@ -0,0 +1,45 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use casper_event_standard::{Event, Schemas};
+use casper_types::account::AccountHash;
+
+/// Emitted when a revocation list is registered with `add_list`.
+#[derive(Event)]
+pub struct ListAdded {
+    pub id: String,
+    pub actor: AccountHash,
+}
+
+/// Emitted when a credential is revoked with `revoke`.
+#[derive(Event)]
+pub struct Revoked {
+    pub id: String,
+    pub actor: AccountHash,
+    pub indices: Vec<u64>,
+}
+
+/// Emitted when a credential is reset with `reset`.
+#[derive(Event)]
+pub struct Reset {
+    pub id: String,
+    pub actor: AccountHash,
+    pub indices: Vec<u64>,
+}
+
+/// Emitted when a revocation list is updated with `update`.
+#[derive(Event)]
+pub struct Updated {
+    pub id: String,
+    pub actor: AccountHash,
+    pub revoked: Vec<u64>,
+    pub reset: Vec<u64>,
+}
+
+/// Returns the schemas of the events emitted by the contract.
+pub fn schemas() -> Schemas {
+    Schemas::new()
+        .with::<ListAdded>()
+        .with::<Revoked>()
+        .with::<Reset>()
+        .with::<Updated>()
+}
@@ -8,240 +8,594 @@ compile_error!("target arch should be wasm32: compile with '--target wasm32-unkn
 // `no_std` environment.
 extern crate alloc;
 
+mod events;
+mod model;
+mod utils;
+
 // Importing Rust types.
+use alloc::boxed::Box;
 use alloc::string::{String, ToString};
 use alloc::vec;
+use alloc::vec::Vec;
 // Importing aspects of the Casper platform.
-use casper_contract::contract_api::storage::dictionary_get;
 use casper_contract::contract_api::{runtime, storage, system};
 use casper_contract::unwrap_or_revert::UnwrapOrRevert;
 // Importing specific Casper types.
 use casper_types::account::AccountHash;
+use casper_types::bytesrepr::FromBytes;
 use casper_types::contracts::NamedKeys;
 use casper_types::{
-    runtime_args, ApiError, CLType, CLValue, DeployHash, EntryPoint, EntryPointAccess,
-    EntryPointType, EntryPoints, Key, Parameter, RuntimeArgs,
+    runtime_args, ApiError, CLType, CLTyped, CLValue, ContractHash, ContractPackageHash,
+    EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Key, Parameter, RuntimeArgs, URef,
+    U512,
 };
-// Import model
-use crate::model::RL2020;
+// Import events and model
+use crate::events::{ListAdded, Reset, Revoked, Updated};
+use crate::model::{DictionaryStore, RLError, DEFAULT_BITSTRING_SIZE_KN, MAX_BITSTRING_SIZE_KB};
+use crate::utils::get_optional_named_arg;
+use rl2020_core::{validate_list_id, ListStore};
 
 // RL2020 entrypoints
+const ENTRY_POINT_INIT: &str = "init";
+const ENTRY_POINT_MIGRATE: &str = "migrate";
 const ENTRY_POINT_ADD_LIST: &str = "add_list";
 const ENTRY_POINT_GET_ENCODED_LIST: &str = "get_encoded_list";
 const ENTRY_POINT_IS_REVOKED: &str = "is_revoked";
 const ENTRY_POINT_REVOKE: &str = "revoke";
 const ENTRY_POINT_RESET: &str = "reset";
+const ENTRY_POINT_UPDATE: &str = "update";
+const ENTRY_POINT_ADD_ADMIN: &str = "add_admin";
+const ENTRY_POINT_REMOVE_ADMIN: &str = "remove_admin";
+const ENTRY_POINT_SET_DEFAULT_LIST_SIZE: &str = "set_default_list_size";
+const ENTRY_POINT_SET_PAUSED: &str = "set_paused";
+const ENTRY_POINT_SET_REGISTRATION_FEE: &str = "set_registration_fee";
+const ENTRY_POINT_WITHDRAW_FEES: &str = "withdraw_fees";
+const ENTRY_POINT_GET_FEES_COLLECTED: &str = "get_fees_collected";
 
 // RL2020 constants
 const DICTIONARY_REVOCATION_LITS: &str = "rls";
+const DICTIONARY_LEGACY_REVOCATION_LISTS: &str = "legacy_rls";
+const RLS_HASHED_KEYS: &str = "rls_hashed_keys";
 const PROTOCOL_TEAM_ACCOUNT: &str = "team_account";
+const ADMIN: &str = "admin";
+const ADMINS: &str = "admins";
+const DEFAULT_LIST_SIZE: &str = "default_list_size";
+const PAUSED: &str = "paused";
+const REGISTRATION_FEE: &str = "registration_fee";
+const CONTRACT_PACKAGE_HASH: &str = "rl2020_package_hash";
+const CONTRACT_ACCESS_UREF: &str = "rl2020_access_uref";
+const CONTRACT_HASH: &str = "rl2020_contract_hash";
+const CONTRACT_VERSION: &str = "rl2020_contract_version";
+const CONTRACT_RLS_UREF: &str = "rl2020_rls_uref";
 
 // RL2020 named arguments
 const PARAM_REVOCATION_LIST_ID: &str = "id";
-
-// // Creating constants for the various contract entry points.
-// const ENTRY_POINT_INIT: &str = "init";
-// const ENTRY_POINT_DONATE: &str = "donate";
-// const ENTRY_POINT_GET_DONATION_COUNT: &str = "get_donation_count";
-// const ENTRY_POINT_GET_FUNDS_RAISED: &str = "get_funds_raised";
-
-// // Creating constants for values within the contract.
-// const DONATING_ACCOUNT_KEY: &str = "donating_account_key";
-// const LEDGER: &str = "ledger";
-// const FUNDRAISING_PURSE: &str = "fundraising_purse";
-// 300_000_000
+const PARAM_INDEX: &str = "idx";
+const PARAM_TO_REVOKE: &str = "to_revoke";
+const PARAM_TO_RESET: &str = "to_reset";
+const PARAM_SIZE: &str = "size";
+const PARAM_PURSE: &str = "purse";
+const PARAM_AMOUNT: &str = "amount";
+const PARAM_ACCOUNT: &str = "account";
+const PARAM_PAUSED: &str = "paused";
 
 /// An error enum which can be converted to a `u16` so it can be returned as an `ApiError::User`.
+///
+/// The list errors shared with NEAR (`RLError`) are returned as `ApiError::User`
+/// shifted by `RL_ERROR_CODE_OFFSET`, so these codes stay below it.
 #[repr(u16)]
 enum Error {
     KeyAlreadyExists = 0,
-    KeyMismatch = 1,
     InvalidKeyVariant = 2,
-    InvalidParamValue = 3,
-    MissingFundRaisingPurseURef = 4,
     MissingRevocationListsDictionary = 5,
+    NotAdmin = 9,
+    MissingPaymentPurse = 10,
+    InsufficientPayment = 11,
+    LastAdmin = 12,
+    WithdrawalFailed = 13,
 }
 
 impl From<Error> for ApiError {
-    fn from(error: Error) -> rls_uref {
+    fn from(error: Error) -> ApiError {
         ApiError::User(error as u16)
     }
 }
 
-fn update_ledger_record(dictionary_item_key: String) {
-    // Acquiring the LEDGER seed URef to properly assign the dictionary item.
-    let ledger_seed_uref = *runtime::get_key(LEDGER)
+/// Returns the dictionary holding the revocation lists, with the legacy dictionary of the
+/// lists stored under their raw ids if the contract was upgraded from such a version.
+fn rls() -> DictionaryStore {
+    let rls_uref: URef = *runtime::get_key(DICTIONARY_REVOCATION_LITS)
         .unwrap_or_revert_with(Error::MissingRevocationListsDictionary)
         .as_uref()
-        .unwrap_or_revert();
+        .unwrap_or_revert_with(Error::InvalidKeyVariant);
+    let legacy_uref = runtime::get_key(DICTIONARY_LEGACY_REVOCATION_LISTS).map(|key| {
+        *key.as_uref()
+            .unwrap_or_revert_with(Error::InvalidKeyVariant)
+    });
+    DictionaryStore(rls_uref, legacy_uref)
+}
+
+/// Returns the URef stored under the given named key.
+fn named_uref(name: &str) -> URef {
+    *runtime::get_key(name)
+        .unwrap_or_revert_with(ApiError::MissingKey)
+        .as_uref()
+        .unwrap_or_revert_with(Error::InvalidKeyVariant)
+}
+
+/// Reads the value stored under the given named key.
+fn read_key<T: CLTyped + FromBytes>(name: &str) -> T {
+    storage::read(named_uref(name))
+        .unwrap_or_revert()
+        .unwrap_or_revert()
+}
+
+/// Returns the purse collecting the registration fees.
+fn team_purse() -> URef {
+    named_uref(PROTOCOL_TEAM_ACCOUNT)
+}
+
+/// Reverts if the caller is not one of the contract admins.
+fn assert_admin() {
+    if !read_key::<Vec<AccountHash>>(ADMINS).contains(&runtime::get_caller()) {
+        runtime::revert(Error::NotAdmin);
+    }
+}
 
-    // This identifies an item within the dictionary and either rls_urefs or updates the associated value.
-    match storage::dictionary_get::<u64>(ledger_seed_uref, &dictionary_item_key).unwrap_or_revert()
-    {
-        None => storage::dictionary_put(ledger_seed_uref, &dictionary_item_key, 1u64),
-        Some(current_number_of_donations) => storage::dictionary_put(
-            ledger_seed_uref,
-            &dictionary_item_key,
-            current_number_of_donations + 1u64,
-        ),
+/// Reverts if the mutations are paused.
+fn assert_not_paused() {
+    if read_key::<bool>(PAUSED) {
+        runtime::revert(RLError::Paused);
     }
 }
 
-/// This entry point initializes the donation system, setting up the fundraising purse
-/// and creating a dictionary to track the account hashes and the number of donations
-/// made.
+/// This entry point initializes the contract, setting up the protocol team purse,
+/// creating the dictionary to store the revocation lists and registering the event schemas.
+/// The account installing the contract becomes the first admin.
+/// Returns a read only reference to the revocation lists dictionary.
 #[no_mangle]
 pub extern "C" fn init() {
+    if runtime::get_key(DICTIONARY_REVOCATION_LITS).is_some() {
+        runtime::revert(Error::KeyAlreadyExists);
+    }
     // purse to collect the team funding
     let team_account = system::create_purse();
-    runtime::put_key(PROTOCOL_TEAM_ACCOUNT, fundraising_purse.into());
-    // dictionary to collect the revocation lists
-    storage::new_dictionary(DICTIONARY_REVOCATION_LITS).unwrap_or_revert();
+    runtime::put_key(PROTOCOL_TEAM_ACCOUNT, team_account.into());
+    // the admins and the configuration, lists registration is free by default
+    runtime::put_key(
+        ADMINS,
+        storage::new_uref(vec![runtime::get_caller()]).into(),
+    );
+    runtime::put_key(
+        DEFAULT_LIST_SIZE,
+        storage::new_uref(DEFAULT_BITSTRING_SIZE_KN as u32).into(),
+    );
+    runtime::put_key(PAUSED, storage::new_uref(false).into());
+    runtime::put_key(REGISTRATION_FEE, storage::new_uref(U512::zero()).into());
+    // dictionary to collect the revocation lists, under the hashed ids only
+    let rls_uref = storage::new_dictionary(DICTIONARY_REVOCATION_LITS).unwrap_or_revert();
+    runtime::put_key(RLS_HASHED_KEYS, storage::new_uref(true).into());
+    // named keys holding the events, following the Casper Event Standard
+    casper_event_standard::init(events::schemas());
+    runtime::ret(CLValue::from_t(rls_uref.into_read()).unwrap_or_revert())
 }
 
+/// This entry point adds the named keys introduced after the contract was first installed,
+/// it is called by the session code on every upgrade.
+/// The single `admin` of the older versions becomes the first of the `admins`, or the caller
+/// if there was none, and the configuration keys and the registration fee get their
+/// initial values.
+/// The dictionary of the versions storing the lists under their raw ids becomes the legacy
+/// dictionary, and a new one stores the lists under their hashed ids, so that a hashed id
+/// cannot be read as the raw id of another list.
+/// Once the `admins` exist the migration is admin only.
+/// Returns a read only reference to the revocation lists dictionary.
 #[no_mangle]
-pub extern "C" fn add_list() {
-    // get the dictionary uref
-    let rls_uref = *runtime::get_key(PARAM_REVOCATION_LIST_ID)
-        .unwrap_or_revert_with(Error::MissingRevocationListsDictionary)
-        .as_uref()
-        .unwrap_or_revert();
+pub extern "C" fn migrate() {
+    match runtime::get_key(ADMINS) {
+        Some(_) => assert_admin(),
+        None => {
+            let admin = match runtime::get_key(ADMIN) {
+                Some(_) => {
+                    let admin: AccountHash = read_key(ADMIN);
+                    runtime::remove_key(ADMIN);
+                    admin
+                }
+                None => runtime::get_caller(),
+            };
+            runtime::put_key(ADMINS, storage::new_uref(vec![admin]).into());
+        }
+    }
+    if runtime::get_key(DEFAULT_LIST_SIZE).is_none() {
+        runtime::put_key(
+            DEFAULT_LIST_SIZE,
+            storage::new_uref(DEFAULT_BITSTRING_SIZE_KN as u32).into(),
+        );
+    }
+    if runtime::get_key(PAUSED).is_none() {
+        runtime::put_key(PAUSED, storage::new_uref(false).into());
+    }
+    if runtime::get_key(REGISTRATION_FEE).is_none() {
+        runtime::put_key(REGISTRATION_FEE, storage::new_uref(U512::zero()).into());
+    }
+    if runtime::get_key(RLS_HASHED_KEYS).is_none() {
+        let legacy_uref = rls().0;
+        runtime::remove_key(DICTIONARY_REVOCATION_LITS);
+        runtime::put_key(DICTIONARY_LEGACY_REVOCATION_LISTS, legacy_uref.into());
+        storage::new_dictionary(DICTIONARY_REVOCATION_LITS).unwrap_or_revert();
+        runtime::put_key(RLS_HASHED_KEYS, storage::new_uref(true).into());
+    }
+    let rls_uref = rls().0;
+    runtime::ret(CLValue::from_t(rls_uref.into_read()).unwrap_or_revert())
+}
 
+/// Registers a new revocation list, the caller becomes the list owner.
+/// The list size in kilobytes can be set with the optional `size` argument,
+/// the configured default size is used otherwise.
+/// If a registration fee is set, it is paid from the `purse` argument into the team purse.
+#[no_mangle]
+pub extern "C" fn add_list() {
+    assert_not_paused();
     // get the named parameter value
-    let revocation_list_id: Key = runtime::get_named_arg(PARAM_REVOCATION_LIST_ID);
-    if revocation_list_id.to_string().trim().is_empty() {
-        *runtime::revert(Error::InvalidParamValue);
+    let id: String = runtime::get_named_arg(PARAM_REVOCATION_LIST_ID);
+    // reject the invalid ids before collecting the fee
+    validate_list_id(&id).unwrap_or_revert();
+    let size = get_optional_named_arg::<u32>(PARAM_SIZE)
+        .unwrap_or_else(|| read_key(DEFAULT_LIST_SIZE)) as usize;
+    if size > MAX_BITSTRING_SIZE_KB {
+        runtime::revert(RLError::ListTooLarge {
+            size,
+            max: MAX_BITSTRING_SIZE_KB,
+        });
+    }
+    // collect the registration fee
+    let fee: U512 = read_key(REGISTRATION_FEE);
+    if !fee.is_zero() {
+        let purse: URef =
+            get_optional_named_arg(PARAM_PURSE).unwrap_or_revert_with(Error::MissingPaymentPurse);
+        if system::get_purse_balance(purse).unwrap_or_default() < fee {
+            runtime::revert(Error::InsufficientPayment);
+        }
+        system::transfer_from_purse_to_purse(purse, team_purse(), fee, None)
+            .unwrap_or_revert_with(Error::InsufficientPayment);
     }
     // if the key exists return error (even if from the list owner)
     // TODO: consider if the owner can replace it's own list,
     // pros => list can be reused, somewhat better privacy
     // cons => can break user space
-    match storage::dictionary_get(rls_uref, revocation_list_id)
-        .unwrap_or_revert_with(Error::MissingRevocationListsDictionary)
-    {
-        Some(_) => *runtime::revert(Error::KeyAlreadyExists),
-        None => {
-            let rl = RL2020::new().ok(); // TODO: this is always ok
-            storage::dictionary_put(rls_uref, id, rl).unwrap_or_revert();
-        }
+    rls().register_list(&id, size).unwrap_or_revert();
+    casper_event_standard::emit(ListAdded {
+        id,
+        actor: runtime::get_caller(),
+    });
+}
+
+/// Returns the encoded (compressed and base64 encoded) revocation list.
+#[no_mangle]
+pub extern "C" fn get_encoded_list() {
+    let id: String = runtime::get_named_arg(PARAM_REVOCATION_LIST_ID);
+    let rl = rls().load_list(&id).unwrap_or_revert();
+    runtime::ret(CLValue::from_t(rl.encoded_list()).unwrap_or_revert())
+}
+
+/// Returns whether the credential at the given index has been revoked.
+#[no_mangle]
+pub extern "C" fn is_revoked() {
+    let id: String = runtime::get_named_arg(PARAM_REVOCATION_LIST_ID);
+    let idx: u64 = runtime::get_named_arg(PARAM_INDEX);
+    let revoked = rls()
+        .load_list(&id)
+        .and_then(|rl| rl.is_revoked(idx))
+        .unwrap_or_revert();
+    runtime::ret(CLValue::from_t(revoked).unwrap_or_revert())
+}
+
+/// Revokes the credential at the given index, only the list owner can revoke.
+#[no_mangle]
+pub extern "C" fn revoke() {
+    assert_not_paused();
+    let id: String = runtime::get_named_arg(PARAM_REVOCATION_LIST_ID);
+    let idx: u64 = runtime::get_named_arg(PARAM_INDEX);
+    rls().update_list(&id, vec![idx], vec![]).unwrap_or_revert();
+    casper_event_standard::emit(Revoked {
+        id,
+        actor: runtime::get_caller(),
+        indices: vec![idx],
+    });
+}
+
+/// Resets the credential at the given index to not revoked, only the list owner can reset.
+#[no_mangle]
+pub extern "C" fn reset() {
+    assert_not_paused();
+    let id: String = runtime::get_named_arg(PARAM_REVOCATION_LIST_ID);
+    let idx: u64 = runtime::get_named_arg(PARAM_INDEX);
+    rls().update_list(&id, vec![], vec![idx]).unwrap_or_revert();
+    casper_event_standard::emit(Reset {
+        id,
+        actor: runtime::get_caller(),
+        indices: vec![idx],
+    });
+}
+
+/// Atomically updates a revocation list, resets take precedence over revocations.
+/// Only the list owner can update the list.
+#[no_mangle]
+pub extern "C" fn update() {
+    assert_not_paused();
+    let id: String = runtime::get_named_arg(PARAM_REVOCATION_LIST_ID);
+    let to_revoke: Vec<u64> = runtime::get_named_arg(PARAM_TO_REVOKE);
+    let to_reset: Vec<u64> = runtime::get_named_arg(PARAM_TO_RESET);
+    rls()
+        .update_list(&id, to_revoke.clone(), to_reset.clone())
+        .unwrap_or_revert();
+    casper_event_standard::emit(Updated {
+        id,
+        actor: runtime::get_caller(),
+        revoked: to_revoke,
+        reset: to_reset,
+    });
+}
+
+/// Adds an account to the contract admins, admin only.
+#[no_mangle]
+pub extern "C" fn add_admin() {
+    assert_admin();
+    let account: AccountHash = runtime::get_named_arg(PARAM_ACCOUNT);
+    let mut admins: Vec<AccountHash> = read_key(ADMINS);
+    if !admins.contains(&account) {
+        admins.push(account);
+        storage::write(named_uref(ADMINS), admins);
     }
 }
 
-// This is the donation entry point. When called, it records the caller's account
-// hash and returns the donation purse, with add access, to the immediate caller.
+/// Removes an account from the contract admins, admin only.
+/// The last admin cannot be removed.
 #[no_mangle]
-pub extern "C" fn donate() {
-    let donating_account_key: Key = runtime::get_named_arg(DONATING_ACCOUNT_KEY);
-    if let Key::Account(donating_account_hash) = donating_account_key {
-        update_ledger_record(donating_account_hash.to_string())
-    } else {
-        runtime::revert(Error::InvalidKeyVariant)
+pub extern "C" fn remove_admin() {
+    assert_admin();
+    let account: AccountHash = runtime::get_named_arg(PARAM_ACCOUNT);
+    let mut admins: Vec<AccountHash> = read_key(ADMINS);
+    admins.retain(|admin| *admin != account);
+    if admins.is_empty() {
+        runtime::revert(Error::LastAdmin);
     }
-    let donation_purse = *runtime::get_key(FUNDRAISING_PURSE)
-        .unwrap_or_revert_with(Error::MissingFundRaisingPurseURef)
-        .as_uref()
-        .unwrap_or_revert();
-    // The return value is the donation_purse URef with `add` access only. As a result
-    // the entity receiving this purse URef may only add to the purse, and cannot remove
-    // funds.
-    let value = CLValue::from_t(donation_purse.into_add()).unwrap_or_revert();
-    runtime::ret(value)
-}
-
-// This entry point returns the amount of donations from the caller.
-#[no_mangle]
-pub extern "C" fn get_donation_count() {
-    let donating_account_key: Key = runtime::get_named_arg(DONATING_ACCOUNT_KEY);
-    if let Key::Account(donating_account_hash) = donating_account_key {
-        let ledger_seed_uref = *runtime::get_key(LEDGER)
-            .unwrap_or_revert_with(Error::MissingRevocationListsDictionary)
-            .as_uref()
-            .unwrap_or_revert();
-        let donation_count = if let Some(donation_count) = storage::dictionary_get::<rls_uref>(
-            ledger_seed_uref,
-            &donating_account_hash.to_string(),
-        )
-        .unwrap_or_revert()
-        {
-            donation_count
-        } else {
-            0u64
-        };
-        runtime::ret(CLValue::from_t(donation_count).unwrap_or_revert())
-    } else {
-        runtime::revert(Error::InvalidKeyVariant)
+    storage::write(named_uref(ADMINS), admins);
+}
+
+/// Sets the size in kilobytes of the lists registered without a `size`, admin only.
+#[no_mangle]
+pub extern "C" fn set_default_list_size() {
+    assert_admin();
+    let size: u32 = runtime::get_named_arg(PARAM_SIZE);
+    if size == 0 || size as usize > MAX_BITSTRING_SIZE_KB {
+        runtime::revert(RLError::InvalidConfig(
+            "default list size must be between 1 and the max list size",
+        ));
     }
+    storage::write(named_uref(DEFAULT_LIST_SIZE), size);
 }
 
-// This entry point returns the total funds raised.
+/// Pauses or resumes all the list mutations, admin only.
 #[no_mangle]
-pub extern "C" fn get_funds_raised() {
-    let donation_purse = *runtime::get_key(FUNDRAISING_PURSE)
-        .unwrap_or_revert_with(Error::MissingFundRaisingPurseURef)
-        .as_uref()
-        .unwrap_or_revert();
-    let funds_raised = system::get_purse_balance(donation_purse).unwrap_or_revert();
-    runtime::ret(CLValue::from_t(funds_raised).unwrap_or_revert())
+pub extern "C" fn set_paused() {
+    assert_admin();
+    let paused: bool = runtime::get_named_arg(PARAM_PAUSED);
+    storage::write(named_uref(PAUSED), paused);
 }
 
-//This is the full `call` function as defined within the donation contract.
+/// Sets the fee to register a list, admin only.
 #[no_mangle]
-pub extern "C" fn call() {
+pub extern "C" fn set_registration_fee() {
+    assert_admin();
+    let fee: U512 = runtime::get_named_arg(PARAM_AMOUNT);
+    storage::write(named_uref(REGISTRATION_FEE), fee);
+}
+
+/// Transfers the collected fees to the calling admin account, admin only.
+/// The whole balance is withdrawn unless an `amount` is provided, an amount above the
+/// balance reverts with `WithdrawalFailed`.
+#[no_mangle]
+pub extern "C" fn withdraw_fees() {
+    assert_admin();
+    let purse = team_purse();
+    let amount: U512 = get_optional_named_arg(PARAM_AMOUNT)
+        .unwrap_or_else(|| system::get_purse_balance(purse).unwrap_or_revert());
+    system::transfer_from_purse_to_account(purse, runtime::get_caller(), amount, None)
+        .unwrap_or_revert_with(Error::WithdrawalFailed);
+}
+
+/// Returns the balance of the fees collected in the team purse.
+#[no_mangle]
+pub extern "C" fn get_fees_collected() {
+    let balance = system::get_purse_balance(team_purse()).unwrap_or_revert();
+    runtime::ret(CLValue::from_t(balance).unwrap_or_revert())
+}
+
+/// Returns the entry points of the contract.
+fn entry_points() -> EntryPoints {
+    let id_param = || Parameter::new(PARAM_REVOCATION_LIST_ID, CLType::String);
+    let idx_param = || Parameter::new(PARAM_INDEX, CLType::U64);
+
+    let mut entry_points = EntryPoints::new();
     // This establishes the `init` entry point for initializing the contract's infrastructure.
-    let init_entry_point = EntryPoint::new(
+    entry_points.add_entry_point(EntryPoint::new(
         ENTRY_POINT_INIT,
         vec![],
-        CLType::Unit,
+        CLType::URef,
         EntryPointAccess::Public,
         EntryPointType::Contract,
-    );
-
-    // This establishes the `donate` entry point for callers looking to donate.
-    let donate_entry_point = EntryPoint::new(
-        ENTRY_POINT_DONATE,
-        vec![Parameter::new(DONATING_ACCOUNT_KEY, CLType::Key)],
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_MIGRATE,
+        vec![],
         CLType::URef,
         EntryPointAccess::Public,
         EntryPointType::Contract,
-    );
-
-    // This establishes an entry point called `donation_count` that returns the amount of
-    // donations from a specific account.
-    let get_donation_count_entry_point = EntryPoint::new(
-        ENTRY_POINT_GET_DONATION_COUNT,
-        vec![Parameter::new(DONATING_ACCOUNT_KEY, CLType::Key)],
-        CLType::U64,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_ADD_LIST,
+        vec![
+            id_param(),
+            Parameter::new(PARAM_SIZE, CLType::Option(Box::new(CLType::U32))),
+            Parameter::new(PARAM_PURSE, CLType::Option(Box::new(CLType::URef))),
+        ],
+        CLType::Unit,
         EntryPointAccess::Public,
         EntryPointType::Contract,
-    );
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_GET_ENCODED_LIST,
+        vec![id_param()],
+        CLType::String,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_IS_REVOKED,
+        vec![id_param(), idx_param()],
+        CLType::Bool,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_REVOKE,
+        vec![id_param(), idx_param()],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_RESET,
+        vec![id_param(), idx_param()],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_UPDATE,
+        vec![
+            id_param(),
+            Parameter::new(PARAM_TO_REVOKE, Vec::<u64>::cl_type()),
+            Parameter::new(PARAM_TO_RESET, Vec::<u64>::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
 
-    // This establishes an entry point called `funds_raised` that returns the total amount
-    // donated by all participants.
-    let funds_raised_entry_point = EntryPoint::new(
-        ENTRY_POINT_GET_FUNDS_RAISED,
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_ADD_ADMIN,
+        vec![Parameter::new(PARAM_ACCOUNT, AccountHash::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_REMOVE_ADMIN,
+        vec![Parameter::new(PARAM_ACCOUNT, AccountHash::cl_type())],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_SET_DEFAULT_LIST_SIZE,
+        vec![Parameter::new(PARAM_SIZE, CLType::U32)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_SET_PAUSED,
+        vec![Parameter::new(PARAM_PAUSED, CLType::Bool)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_SET_REGISTRATION_FEE,
+        vec![Parameter::new(PARAM_AMOUNT, CLType::U512)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_WITHDRAW_FEES,
+        vec![Parameter::new(
+            PARAM_AMOUNT,
+            CLType::Option(Box::new(CLType::U512)),
+        )],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    ));
+    entry_points.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_GET_FEES_COLLECTED,
         vec![],
         CLType::U512,
         EntryPointAccess::Public,
         EntryPointType::Contract,
-    );
+    ));
 
-    let mut entry_points = EntryPoints::new();
-    entry_points.add_entry_point(init_entry_point);
-    entry_points.add_entry_point(donate_entry_point);
-    entry_points.add_entry_point(get_donation_count_entry_point);
-    entry_points.add_entry_point(funds_raised_entry_point);
+    entry_points
+}
 
+/// Installs the contract and calls the `init` entry point.
+fn install() {
     let named_keys = NamedKeys::new();
 
-    let (contract_hash, _contract_version) = storage::new_contract(
-        entry_points,
+    let (contract_hash, contract_version) = storage::new_contract(
+        entry_points(),
         Some(named_keys),
-        Some("fundraiser_package_hash".to_string()),
-        Some("fundraiser_access_uref".to_string()),
+        Some(CONTRACT_PACKAGE_HASH.to_string()),
+        Some(CONTRACT_ACCESS_UREF.to_string()),
     );
 
-    runtime::put_key("fundraiser_contract_hash", contract_hash.into());
-    // Call the init entry point to setup and create the fundraising purse
-    // and the ledger to track donations made.
-    runtime::call_contract::<()>(contract_hash, ENTRY_POINT_INIT, runtime_args! {})
+    runtime::put_key(CONTRACT_HASH, contract_hash.into());
+    runtime::put_key(CONTRACT_VERSION, storage::new_uref(contract_version).into());
+    // Call the init entry point to setup the team purse, the revocation lists dictionary
+    // and the event schemas.
+    let rls_uref: URef = runtime::call_contract(contract_hash, ENTRY_POINT_INIT, runtime_args! {});
+    // the lists can be read by the clients with `state_get_dictionary_item`
+    // using this reference and the `dictionary_item_key` of the list id
+    runtime::put_key(CONTRACT_RLS_UREF, rls_uref.into());
+}
+
+/// Adds a new version of the contract to the existing package.
+///
+/// The named keys of the previous version (the revocation lists dictionary, the team purse
+/// and the events) are carried over to the new version by `add_contract_version`, so `init`
+/// must not be called again. The keys added by the newer versions are created by `migrate`
+/// in the same deploy, before anyone else can call the new version.
+/// The previous version is disabled, so that it cannot change the lists anymore.
+fn upgrade(package_key: Key) {
+    let package_hash = package_key
+        .into_hash()
+        .map(ContractPackageHash::new)
+        .unwrap_or_revert_with(Error::InvalidKeyVariant);
+    let previous_hash = runtime::get_key(CONTRACT_HASH)
+        .and_then(Key::into_hash)
+        .map(ContractHash::new)
+        .unwrap_or_revert_with(Error::InvalidKeyVariant);
+
+    let (contract_hash, contract_version) =
+        storage::add_contract_version(package_hash, entry_points(), NamedKeys::new());
+
+    runtime::put_key(CONTRACT_HASH, contract_hash.into());
+    runtime::put_key(CONTRACT_VERSION, storage::new_uref(contract_version).into());
+    let rls_uref: URef =
+        runtime::call_contract(contract_hash, ENTRY_POINT_MIGRATE, runtime_args! {});
+    // the versions installed before the lists could be read from the global state
+    // did not give this reference to the installing account
+    runtime::put_key(CONTRACT_RLS_UREF, rls_uref.into());
+    storage::disable_contract_version(package_hash, previous_hash).unwrap_or_revert();
+}
+
+/// Installs the contract, or upgrades it if the caller already installed the package.
+#[no_mangle]
+pub extern "C" fn call() {
+    match runtime::get_key(CONTRACT_PACKAGE_HASH) {
+        Some(package_key) => upgrade(package_key),
+        None => install(),
+    }
 }
@@ -1,136 +1,63 @@
-use std::io::{Read, Write};
+use alloc::format;
+use alloc::string::String;
+use casper_contract::contract_api::{runtime, storage};
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+use casper_types::account::AccountHash;
+use casper_types::{URef, DICTIONARY_ITEM_KEY_MAX_LENGTH};
+use rl2020_core::{Chain, ListStore};
+
+/// the revocation lists errors, shared with the NEAR contract
+pub use rl2020_core::RLError;
+
+/// the initial default size in kilobytes of the lists registered with `add_list`
+pub const DEFAULT_BITSTRING_SIZE_KN: usize = 16;
+/// the max size in kilobytes of the lists registered with `add_list`
+pub const MAX_BITSTRING_SIZE_KB: usize = 64;
 
-use base64::{decode_config, encode_config, STANDARD};
-use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
+/// this comes from https://github.com/noandrea/rl2020.rs
+pub type RL2020 = rl2020_core::RL2020<AccountHash>;
 
-const DEFAULT_BITSTRING_SIZE_KN: usize = 16;
+/// the Casper implementation of the chain specific concerns of the lists
+pub struct Casper;
 
-#[derive(Debug)]
-pub struct RLError {
-    pub message: String,
-}
+impl Chain for Casper {
+    type AccountId = AccountHash;
 
-impl RLError {
-    pub fn new(msg: &str) -> Self {
-        RLError {
-            message: String::from(msg),
-        }
+    fn caller() -> AccountHash {
+        runtime::get_caller()
     }
 }
 
-/// this comes from https://github.com/noandrea/rl2020.rs
-#[derive(Clone, Default, Serialize, Deserialize)]
-#[serde(crate = "near_sdk::serde")]
-pub struct RL2020 {
-    pub encoded_list: String,
-    pub creator: AccountId,
+/// returns the dictionary item key of a list, the hex encoded blake2b hash of the list id.
+/// List ids can be longer than the 64 bytes allowed for the dictionary item keys, hashing
+/// them gives the clients a deterministic key to read the lists with `state_get_dictionary_item`
+pub fn dictionary_item_key(id: &str) -> String {
+    runtime::blake2b(id)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
 }
 
-/// Reference implementation here
-/// https://github.com/noandrea/rl2020.rs/blob/ab747623429438334484df308884bd9da4c06e93/src/lib.rs#L105
-///
-///
-impl RL2020 {
-    pub fn new(id: String) -> Result<Self, RLError> {
-        // if size < MIN_BITSTRING_SIZE_KN {
-        //     return Err(RLError::new(&format!(
-        //         "minimum credential size is {}, got {}",
-        //         MIN_BITSTRING_SIZE_KN, size
-        //     )));
-        // }
-        // if size > MAX_BITSTRING_SIZE_KB {
-        //     return Err(RLError::new(&format!(
-        //         "maximum credential size is {}, got {}",
-        //         MIN_BITSTRING_SIZE_KN, size
-        //     )));
-        // }
-
-        // initialize the bitset
-        let bs = vec![0; DEFAULT_BITSTRING_SIZE_KN * 1024];
-        let el = Self::pack(&bs)?;
-        Ok(RL2020 {
-            encoded_list: el,
-            creator: env::predecessor_account_id().to_string(),
-        })
-    }
-
-    fn pack(data: &Vec<u8>) -> Result<String, RLError> {
-        // compress the data
-        let mut e = ZlibEncoder::new(Vec::new(), Compression::default());
-        e.write_all(data)
-            .map_err(|e| RLError::new(&e.to_string()))?;
-        let compressed = e.finish().map_err(|e| RLError::new(&e.to_string()))?;
-        // encode the data
-        Ok(encode_config(&compressed, STANDARD))
-    }
-
-    fn unpack(data: &String) -> Result<Vec<u8>, RLError> {
-        let bin = decode_config(&data, STANDARD).map_err(|e| RLError::new(&e.to_string()))?;
-        let mut d = ZlibDecoder::new(&*bin);
-        let mut buf = Vec::new();
-        d.read_to_end(&mut buf)
-            .map_err(|e| RLError::new(&e.to_string()))?;
-        Ok(buf)
-    }
-
-    fn check_bounds(&self, index: u64) -> Result<(), RLError> {
-        match index {
-            i if (i as usize) >= self.capacity() => Err(RLError::new(&format!(
-                "max indexable element is {}, provided index {} is out of range",
-                self.capacity(),
-                i,
-            ))),
-            _ => Ok(()),
-        }
-    }
-
-    pub fn capacity(&self) -> usize {
-        DEFAULT_BITSTRING_SIZE_KN * 1024 * 8
-    }
-
-    // size returns the size of the bitset int kb
-    pub fn size(&self) -> usize {
-        return DEFAULT_BITSTRING_SIZE_KN;
-    }
-
-    pub fn set(&mut self, revoke: bool, index: u64) -> Result<(), RLError> {
-        self.check_bounds(index)?;
-
-        let pos = (index / 8) as usize;
-        let j = (index % 8) as u8;
-
-        let mut bit_set = Self::unpack(&self.encoded_list)?;
-        match revoke {
-            true => bit_set[pos] |= 1 << j,
-            false => bit_set[pos] &= !(1 << j),
-        };
-        self.encoded_list = Self::pack(&bit_set)?;
-        Ok(())
-    }
-
-    pub fn get(&self, index: u64) -> Result<bool, RLError> {
-        self.check_bounds(index)?;
-
-        let pos = (index / 8) as usize;
-        let j = (index % 8) as u8;
-
-        let bit_set = Self::unpack(&self.encoded_list)?;
-
-        match bit_set[pos] & (1 << j) {
-            0 => Ok(false),
-            _ => Ok(true),
-        }
-    }
-
-    pub fn revoke(&mut self, idx: u64) -> Result<(), RLError> {
-        self.set(true, idx)
-    }
-
-    pub fn reset(&mut self, idx: u64) -> Result<(), RLError> {
-        self.set(false, idx)
-    }
-
-    pub fn is_revoked(&self, idx: u64) -> Result<bool, RLError> {
-        return self.get(idx);
+/// the revocation lists stored in a dictionary, keyed by `dictionary_item_key`.
+/// The lists registered before the ids were hashed are stored under the raw id in the legacy
+/// dictionary, kept by the upgrade, and are still found there, they move to the hashed key
+/// the first time they are updated
+pub struct DictionaryStore(pub URef, pub Option<URef>);
+
+impl ListStore<Casper> for DictionaryStore {
+    fn get_list(&self, id: &str) -> Option<RL2020> {
+        storage::dictionary_get(self.0, &dictionary_item_key(id))
+            .unwrap_or_revert()
+            .or_else(|| {
+                let legacy = self.1?;
+                if id.len() > DICTIONARY_ITEM_KEY_MAX_LENGTH {
+                    return None;
+                }
+                storage::dictionary_get(legacy, id).unwrap_or_revert()
+            })
+    }
+
+    fn put_list(&mut self, id: &str, rl: &RL2020) {
+        storage::dictionary_put(self.0, &dictionary_item_key(id), rl.clone());
     }
 }
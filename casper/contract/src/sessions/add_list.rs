@@ -0,0 +1,43 @@
+//! Session code to register a revocation list paying the registration fee.
+//!
+//! Stored contracts cannot withdraw from the main purse of the caller, so the fee is moved
+//! into a new purse that is handed to the `add_list` entry point.
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+extern crate alloc;
+
+use alloc::string::String;
+use casper_contract::contract_api::{account, runtime, system};
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+use casper_types::{runtime_args, ContractHash, RuntimeArgs, U512};
+
+const ENTRY_POINT_ADD_LIST: &str = "add_list";
+
+const PARAM_CONTRACT_HASH: &str = "contract_hash";
+const PARAM_REVOCATION_LIST_ID: &str = "id";
+const PARAM_AMOUNT: &str = "amount";
+const PARAM_PURSE: &str = "purse";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_hash: ContractHash = runtime::get_named_arg(PARAM_CONTRACT_HASH);
+    let id: String = runtime::get_named_arg(PARAM_REVOCATION_LIST_ID);
+    let amount: U512 = runtime::get_named_arg(PARAM_AMOUNT);
+
+    let purse = system::create_purse();
+    system::transfer_from_purse_to_purse(account::get_main_purse(), purse, amount, None)
+        .unwrap_or_revert();
+
+    runtime::call_contract::<()>(
+        contract_hash,
+        ENTRY_POINT_ADD_LIST,
+        runtime_args! {
+            PARAM_REVOCATION_LIST_ID => id,
+            PARAM_PURSE => purse,
+        },
+    )
+}
@@ -0,0 +1,43 @@
+//! Session code to update several revocation lists in a single deploy.
+//!
+//! The `updates` argument is a list of `(list_id, to_revoke, to_reset)` tuples, each one is
+//! applied with the `update` entry point of the contract. Any failure reverts the whole deploy,
+//! so either all the lists are updated or none is.
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use casper_contract::contract_api::runtime;
+use casper_types::{runtime_args, ContractHash, RuntimeArgs};
+
+const ENTRY_POINT_UPDATE: &str = "update";
+
+const PARAM_CONTRACT_HASH: &str = "contract_hash";
+const PARAM_UPDATES: &str = "updates";
+const PARAM_REVOCATION_LIST_ID: &str = "id";
+const PARAM_TO_REVOKE: &str = "to_revoke";
+const PARAM_TO_RESET: &str = "to_reset";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_hash: ContractHash = runtime::get_named_arg(PARAM_CONTRACT_HASH);
+    let updates: Vec<(String, Vec<u64>, Vec<u64>)> = runtime::get_named_arg(PARAM_UPDATES);
+
+    for (id, to_revoke, to_reset) in updates {
+        runtime::call_contract::<()>(
+            contract_hash,
+            ENTRY_POINT_UPDATE,
+            runtime_args! {
+                PARAM_REVOCATION_LIST_ID => id,
+                PARAM_TO_REVOKE => to_revoke,
+                PARAM_TO_RESET => to_reset,
+            },
+        );
+    }
+}
@@ -0,0 +1,53 @@
+//! Session code to call a view entry point of the contract and store the returned value.
+//!
+//! The values returned by a stored contract are only visible to the calling code, so the
+//! result is put in the `view_result` named key of the caller where it can be read from
+//! the global state. The `args` argument holds the serialized arguments of the entry point.
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+extern crate alloc;
+
+use alloc::string::String;
+use casper_contract::contract_api::{runtime, storage};
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+use casper_types::bytesrepr::{Bytes, FromBytes, ToBytes};
+use casper_types::{ApiError, CLTyped, ContractHash, RuntimeArgs, U512};
+
+const ENTRY_POINT_GET_ENCODED_LIST: &str = "get_encoded_list";
+const ENTRY_POINT_IS_REVOKED: &str = "is_revoked";
+const ENTRY_POINT_GET_FEES_COLLECTED: &str = "get_fees_collected";
+
+const PARAM_CONTRACT_HASH: &str = "contract_hash";
+const PARAM_ENTRY_POINT: &str = "entry_point";
+const PARAM_ARGS: &str = "args";
+
+const VIEW_RESULT: &str = "view_result";
+
+/// Calls the entry point and stores the value it returns in the named keys of the caller.
+fn store_result<T: CLTyped + FromBytes + ToBytes>(
+    contract_hash: ContractHash,
+    entry_point: &str,
+    args: RuntimeArgs,
+) {
+    let result: T = runtime::call_contract(contract_hash, entry_point, args);
+    runtime::put_key(VIEW_RESULT, storage::new_uref(result).into());
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_hash: ContractHash = runtime::get_named_arg(PARAM_CONTRACT_HASH);
+    let entry_point: String = runtime::get_named_arg(PARAM_ENTRY_POINT);
+    let args: Bytes = runtime::get_named_arg(PARAM_ARGS);
+    let (args, _) = RuntimeArgs::from_bytes(&args).unwrap_or_revert();
+
+    match entry_point.as_str() {
+        ENTRY_POINT_GET_ENCODED_LIST => store_result::<String>(contract_hash, &entry_point, args),
+        ENTRY_POINT_IS_REVOKED => store_result::<bool>(contract_hash, &entry_point, args),
+        ENTRY_POINT_GET_FEES_COLLECTED => store_result::<U512>(contract_hash, &entry_point, args),
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
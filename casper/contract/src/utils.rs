@@ -0,0 +1,39 @@
+use alloc::vec;
+use casper_contract::contract_api::runtime;
+use casper_contract::ext_ffi;
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+use casper_types::{api_error, bytesrepr, bytesrepr::FromBytes, ApiError};
+
+/// Returns the named argument if it was provided by the caller, `None` otherwise.
+/// The argument can be passed either as a value or as an `Option` of it, the way
+/// the optional parameters are declared in the entry points.
+/// Reverts if the argument is present but cannot be deserialized.
+pub fn get_optional_named_arg<T: FromBytes>(name: &str) -> Option<T> {
+    let mut arg_size: usize = 0;
+    let ret = unsafe {
+        ext_ffi::casper_get_named_arg_size(name.as_ptr(), name.len(), &mut arg_size as *mut usize)
+    };
+    match api_error::result_from(ret) {
+        Ok(_) => {}
+        Err(ApiError::MissingArgument) => return None,
+        Err(e) => runtime::revert(e),
+    }
+
+    let mut arg_bytes = vec![0u8; arg_size];
+    if arg_size > 0 {
+        let ret = unsafe {
+            ext_ffi::casper_get_named_arg(
+                name.as_ptr(),
+                name.len(),
+                arg_bytes.as_mut_ptr(),
+                arg_size,
+            )
+        };
+        api_error::result_from(ret).unwrap_or_revert();
+    }
+    match bytesrepr::deserialize::<T>(arg_bytes.clone()) {
+        Ok(value) => Some(value),
+        Err(_) => bytesrepr::deserialize::<Option<T>>(arg_bytes)
+            .unwrap_or_revert_with(ApiError::InvalidArgument),
+    }
+}
@@ -0,0 +1,126 @@
+//! Execution costs of the contract entry points for growing list sizes.
+//!
+//! Run with `make bench` (or `cargo test benchmarks -- --ignored --nocapture`), the table is
+//! printed and written to `target/gas_report.md`.
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+
+use casper_engine_test_support::{InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR};
+use casper_types::{runtime_args, ContractHash, RuntimeArgs, U512};
+
+use crate::test_support::{call, install};
+
+const LIST_SIZES_KB: [u32; 3] = [4, 16, 64];
+const UPDATE_SIZES: [u64; 3] = [1, 10, 100];
+const REPORT_FILE: &str = "target/gas_report.md";
+
+/// Calls an entry point from the default account and returns the execution cost.
+fn cost(
+    builder: &mut InMemoryWasmTestBuilder,
+    contract_hash: ContractHash,
+    entry_point: &str,
+    args: RuntimeArgs,
+) -> U512 {
+    call(
+        builder,
+        *DEFAULT_ACCOUNT_ADDR,
+        contract_hash,
+        entry_point,
+        args,
+    )
+    .expect_success();
+    builder.last_exec_gas_cost().value()
+}
+
+/// Returns `n` indices spread over the list capacity.
+fn spread_indices(size_kb: u32, n: u64) -> Vec<u64> {
+    let capacity = u64::from(size_kb) * 1024 * 8;
+    (0..n).map(|i| i * (capacity / n)).collect()
+}
+
+/// Measures the entry points for a list of the given size, returning the (operation, cost) rows.
+fn measure(size_kb: u32) -> Vec<(String, U512)> {
+    let (mut builder, contract_hash) = install();
+    let id = format!("bench/{}", size_kb);
+    let mut rows = Vec::new();
+
+    let add_list = cost(
+        &mut builder,
+        contract_hash,
+        "add_list",
+        runtime_args! {"id" => id.clone(), "size" => size_kb},
+    );
+    rows.push(("add_list".to_string(), add_list));
+
+    let revoke = cost(
+        &mut builder,
+        contract_hash,
+        "revoke",
+        runtime_args! {"id" => id.clone(), "idx" => 42u64},
+    );
+    rows.push(("revoke".to_string(), revoke));
+
+    let reset = cost(
+        &mut builder,
+        contract_hash,
+        "reset",
+        runtime_args! {"id" => id.clone(), "idx" => 42u64},
+    );
+    rows.push(("reset".to_string(), reset));
+
+    for n in UPDATE_SIZES {
+        let update = cost(
+            &mut builder,
+            contract_hash,
+            "update",
+            runtime_args! {
+                "id" => id.clone(),
+                "to_revoke" => spread_indices(size_kb, n),
+                "to_reset" => Vec::<u64>::new(),
+            },
+        );
+        rows.push((format!("update ({} indices)", n), update));
+    }
+
+    let is_revoked = cost(
+        &mut builder,
+        contract_hash,
+        "is_revoked",
+        runtime_args! {"id" => id, "idx" => 42u64},
+    );
+    rows.push(("is_revoked".to_string(), is_revoked));
+
+    rows
+}
+
+#[test]
+#[ignore = "slow, run with `make bench`"]
+fn entry_point_costs() {
+    let measures: Vec<Vec<(String, U512)>> =
+        LIST_SIZES_KB.iter().map(|size| measure(*size)).collect();
+
+    let mut report = String::from("| entry point |");
+    for size in LIST_SIZES_KB {
+        write!(report, " {} KB |", size).unwrap();
+    }
+    report.push_str("\n|---|");
+    report.push_str(&"---:|".repeat(LIST_SIZES_KB.len()));
+    report.push('\n');
+    for (row, (operation, _)) in measures[0].iter().enumerate() {
+        write!(report, "| {} |", operation).unwrap();
+        for measure in &measures {
+            write!(report, " {} |", measure[row].1).unwrap();
+        }
+        report.push('\n');
+    }
+    println!("{}", report);
+
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(REPORT_FILE);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, report).unwrap();
+
+    // the cost of a mutation grows with the size of the list to unpack and pack
+    let revoke_costs: Vec<U512> = measures.iter().map(|m| m[1].1).collect();
+    assert!(revoke_costs.windows(2).all(|w| w[0] < w[1]));
+}
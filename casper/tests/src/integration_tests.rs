@@ -1,119 +1,1441 @@
+fn main() {
+    panic!("Execute \"cargo test\" to test the contract, not \"cargo run\".");
+}
+
 #[cfg(test)]
-mod tests {
-    use std::path::PathBuf;
+mod benchmarks;
+#[cfg(test)]
+mod test_support;
 
+#[cfg(test)]
+mod tests {
     use casper_engine_test_support::{
-        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
-        DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE, DEFAULT_GENESIS_CONFIG,
-        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
-    };
-    use casper_execution_engine::core::engine_state::{
-        run_genesis_request::RunGenesisRequest, GenesisAccount,
-    };
-    use casper_types::{
-        account::AccountHash, runtime_args, Key, Motes, PublicKey, RuntimeArgs, SecretKey, U512,
+        InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR, DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
     };
+    use casper_event_standard::Event;
+    use casper_types::{account::AccountHash, runtime_args, AccessRights, Key, RuntimeArgs, U512};
 
-    const MY_ACCOUNT: [u8; 32] = [7u8; 32];
-    // Define `KEY` constant to match that in the contract.
-    const KEY: &str = "my-key-name";
-    const VALUE: &str = "hello world";
-    const RUNTIME_ARG_NAME: &str = "message";
-    const CONTRACT_WASM: &str = "contract.wasm";
-
-    #[test]
-    fn should_store_hello_world() {
-        // Create keypair.
-        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
-        let public_key = PublicKey::from(&secret_key);
-
-        // Create an AccountHash from a public key.
-        let account_addr = AccountHash::from(&public_key);
-        // Create a GenesisAccount.
-        let account = GenesisAccount::account(
-            public_key,
-            Motes::new(U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE)),
-            None,
-        );
+    use crate::test_support::*;
+
+    const LIST_ID: &str = "example/rl/1";
+    const OTHER_LIST_ID: &str = "example/rl/2";
+    const OTHER_ACCOUNT_ADDR: AccountHash = AccountHash::new([7u8; 32]);
+
+    // The contract error codes returned as `ApiError::User`.
+    const ERROR_NOT_ADMIN: u16 = 9;
+    const ERROR_MISSING_PAYMENT_PURSE: u16 = 10;
+    const ERROR_INSUFFICIENT_PAYMENT: u16 = 11;
+    const ERROR_LAST_ADMIN: u16 = 12;
+    const ERROR_WITHDRAWAL_FAILED: u16 = 13;
+    // The shared list errors are offset by `RL_ERROR_CODE_OFFSET`.
+    const ERROR_INDEX_OUT_OF_BOUNDS: u16 = 101;
+    const ERROR_INVALID_CONFIG: u16 = 106;
+    const ERROR_INVALID_LIST_ID: u16 = 107;
+    const ERROR_RL_SIZE_TOO_LARGE: u16 = 108;
+    const ERROR_NOT_LIST_OWNER: u16 = 109;
+    const ERROR_LIST_NOT_FOUND: u16 = 110;
+    const ERROR_LIST_EXISTS: u16 = 111;
+    const ERROR_PAUSED: u16 = 112;
+
+    // The encoded lists for a 16kb list, these are the same produced by the NEAR contract.
+    const EMPTY_LIST: &str = "eJztwDEBAAAAwqD1T20MHygAAAAAAAAAAAAAAAAAAADgbUAAAAE=";
+    const LIST_WITH_3214_REVOKED: &str = "eJztwAENAAAIwKBHs38qa7gJxSkTAAAAAAAAAAAAAAAAAAAADy3coQBB";
+
+    // The events emitted by the contract, mirroring the contract definitions.
+    #[derive(Event, Debug, PartialEq)]
+    struct ListAdded {
+        id: String,
+        actor: AccountHash,
+    }
 
-        let mut genesis_config = DEFAULT_GENESIS_CONFIG.clone();
-        genesis_config.ee_config_mut().push_account(account);
+    #[derive(Event, Debug, PartialEq)]
+    struct Revoked {
+        id: String,
+        actor: AccountHash,
+        indices: Vec<u64>,
+    }
+
+    #[derive(Event, Debug, PartialEq)]
+    struct Reset {
+        id: String,
+        actor: AccountHash,
+        indices: Vec<u64>,
+    }
+
+    #[derive(Event, Debug, PartialEq)]
+    struct Updated {
+        id: String,
+        actor: AccountHash,
+        revoked: Vec<u64>,
+        reset: Vec<u64>,
+    }
 
-        let run_genesis_request = RunGenesisRequest::new(
-            *DEFAULT_GENESIS_CONFIG_HASH,
-            genesis_config.protocol_version(),
-            genesis_config.take_ee_config(),
+    #[test]
+    fn should_install_and_add_list() {
+        let (mut builder, contract_hash) = install();
+
+        call(
+            &mut builder,
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            "add_list",
+            runtime_args! {"id" => LIST_ID},
+        )
+        .expect_success();
+
+        let rl = get_list(&builder, contract_hash, LIST_ID);
+        assert_eq!(rl.encoded_list(), EMPTY_LIST);
+        assert_eq!(rl.creator, *DEFAULT_ACCOUNT_ADDR);
+        assert_eq!(rl.size(), 16);
+
+        // lists cannot be registered twice
+        call(
+            &mut builder,
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            "add_list",
+            runtime_args! {"id" => LIST_ID},
+        )
+        .expect_failure();
+
+        // the list size can be chosen up to 64kb
+        call(
+            &mut builder,
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            "add_list",
+            runtime_args! {"id" => OTHER_LIST_ID, "size" => 4u32},
+        )
+        .expect_success();
+        assert_eq!(get_list(&builder, contract_hash, OTHER_LIST_ID).size(), 4);
+        call(
+            &mut builder,
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            "add_list",
+            runtime_args! {"id" => "example/rl/3", "size" => 65u32},
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_RL_SIZE_TOO_LARGE);
+
+        // the optional arguments can also be passed as declared, as options
+        call(
+            &mut builder,
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            "add_list",
+            runtime_args! {"id" => "example/rl/4", "size" => Some(8u32)},
+        )
+        .expect_success();
+        assert_eq!(get_list(&builder, contract_hash, "example/rl/4").size(), 8);
+        call(
+            &mut builder,
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            "add_list",
+            runtime_args! {"id" => "example/rl/5", "size" => Option::<u32>::None},
+        )
+        .expect_success();
+        assert_eq!(get_list(&builder, contract_hash, "example/rl/5").size(), 16);
+    }
+
+    #[test]
+    fn should_reject_invalid_list_ids() {
+        let (mut builder, contract_hash) = install();
+
+        // the same rules as the NEAR contract, see `rl2020_core::validate_list_id`
+        let too_long = "a".repeat(129);
+        let table = [
+            ("acme/employees-2023_v1", true),
+            (&too_long[1..], true),
+            ("", false),
+            (too_long.as_str(), false),
+            (" example/rl/1", false),
+            ("example/rl/1 ", false),
+            ("example/rl/1\n", false),
+            ("example/rl/\u{e9}", false),
+            ("example//rl", false),
+            ("/example/rl", false),
+        ];
+        for (id, valid) in table {
+            call(
+                &mut builder,
+                *DEFAULT_ACCOUNT_ADDR,
+                contract_hash,
+                "add_list",
+                runtime_args! {"id" => id, "size" => 1u32},
+            );
+            match valid {
+                true => builder.expect_success(),
+                false => {
+                    builder.expect_failure();
+                    assert_user_error(&builder, ERROR_INVALID_LIST_ID);
+                    &mut builder
+                }
+            };
+        }
+    }
+
+    #[test]
+    fn should_revoke_and_reset() {
+        let (mut builder, contract_hash) = install();
+        let sender = *DEFAULT_ACCOUNT_ADDR;
+
+        call(
+            &mut builder,
+            sender,
+            contract_hash,
+            "add_list",
+            runtime_args! {"id" => LIST_ID},
+        )
+        .expect_success();
+
+        call(
+            &mut builder,
+            sender,
+            contract_hash,
+            "revoke",
+            runtime_args! {"id" => LIST_ID, "idx" => 3214u64},
+        )
+        .expect_success();
+        let encoded_list: String = call_view(
+            &mut builder,
+            sender,
+            contract_hash,
+            "get_encoded_list",
+            runtime_args! {"id" => LIST_ID},
+            [2u8; 32],
+        );
+        assert_eq!(encoded_list, LIST_WITH_3214_REVOKED);
+        let revoked: bool = call_view(
+            &mut builder,
+            sender,
+            contract_hash,
+            "is_revoked",
+            runtime_args! {"id" => LIST_ID, "idx" => 3214u64},
+            [3u8; 32],
         );
-        // The test framework checks for compiled Wasm files in '<current working dir>/wasm'.  Paths
-        // relative to the current working dir (e.g. 'wasm/contract.wasm') can also be used, as can
-        // absolute paths.
-        let session_code = PathBuf::from(CONTRACT_WASM);
-        let session_args = runtime_args! {
-            RUNTIME_ARG_NAME => VALUE,
-        };
+        assert!(revoked);
 
-        let deploy_item = DeployItemBuilder::new()
-            .with_empty_payment_bytes(runtime_args! {
-                ARG_AMOUNT => *DEFAULT_PAYMENT
-            })
-            .with_session_code(session_code, session_args)
-            .with_authorization_keys(&[account_addr])
-            .with_address(account_addr)
-            .build();
+        call(
+            &mut builder,
+            sender,
+            contract_hash,
+            "reset",
+            runtime_args! {"id" => LIST_ID, "idx" => 3214u64},
+        )
+        .expect_success();
+        let encoded_list: String = call_view(
+            &mut builder,
+            sender,
+            contract_hash,
+            "get_encoded_list",
+            runtime_args! {"id" => LIST_ID},
+            [4u8; 32],
+        );
+        assert_eq!(encoded_list, EMPTY_LIST);
+        let revoked: bool = call_view(
+            &mut builder,
+            sender,
+            contract_hash,
+            "is_revoked",
+            runtime_args! {"id" => LIST_ID, "idx" => 3214u64},
+            [5u8; 32],
+        );
+        assert!(!revoked);
 
-        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
+        call(
+            &mut builder,
+            sender,
+            contract_hash,
+            "update",
+            runtime_args! {
+                "id" => LIST_ID,
+                "to_revoke" => vec![3214u64, 10],
+                "to_reset" => vec![10u64],
+            },
+        )
+        .expect_success();
+        let encoded_list: String = call_view(
+            &mut builder,
+            sender,
+            contract_hash,
+            "get_encoded_list",
+            runtime_args! {"id" => LIST_ID},
+            [6u8; 32],
+        );
+        assert_eq!(encoded_list, LIST_WITH_3214_REVOKED);
+        // the reset of the same update wins over the revocation
+        let revoked: bool = call_view(
+            &mut builder,
+            sender,
+            contract_hash,
+            "is_revoked",
+            runtime_args! {"id" => LIST_ID, "idx" => 10u64},
+            [7u8; 32],
+        );
+        assert!(!revoked);
+        assert_eq!(
+            get_list(&builder, contract_hash, LIST_ID).encoded_list(),
+            encoded_list
+        );
+    }
 
+    #[test]
+    fn should_upgrade_and_keep_the_lists() {
         let mut builder = InMemoryWasmTestBuilder::default();
-        builder.run_genesis(&run_genesis_request).commit();
+        builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST).commit();
+        let sender = *DEFAULT_ACCOUNT_ADDR;
 
-        // prepare assertions.
-        let result_of_query = builder.query(
-            None,
-            Key::Account(*DEFAULT_ACCOUNT_ADDR),
-            &[KEY.to_string()],
+        // the first upgradable version of the contract
+        let v1_hash = deploy_wasm(&mut builder, fixture(WITHOUT_FEES_FIXTURE), [1u8; 32]);
+        call(
+            &mut builder,
+            sender,
+            v1_hash,
+            "add_list",
+            runtime_args! {"id" => LIST_ID},
+        )
+        .expect_success();
+        call(
+            &mut builder,
+            sender,
+            v1_hash,
+            "revoke",
+            runtime_args! {"id" => LIST_ID, "idx" => 3214u64},
+        )
+        .expect_success();
+
+        // running the session again adds a new version to the package
+        let v2_hash = deploy(&mut builder, [2u8; 32]);
+        assert_ne!(v1_hash, v2_hash);
+        let version: u32 = builder
+            .query(None, Key::Account(sender), &[CONTRACT_VERSION.to_string()])
+            .expect("should have the contract version")
+            .as_cl_value()
+            .expect("should be cl value")
+            .clone()
+            .into_t()
+            .expect("should be u32");
+        assert_eq!(version, 2);
+
+        // the previous version is disabled
+        call_version(
+            &mut builder,
+            sender,
+            Some(1),
+            "revoke",
+            runtime_args! {"id" => LIST_ID, "idx" => 1u64},
+        )
+        .expect_failure();
+
+        // the new version reads and updates the lists of the previous one, stored under the
+        // raw ids in the legacy dictionary
+        assert_eq!(
+            contract_key(&builder, v1_hash, DICTIONARY_REVOCATION_LITS),
+            contract_key(&builder, v2_hash, DICTIONARY_LEGACY_REVOCATION_LISTS)
+        );
+        assert_ne!(
+            contract_key(&builder, v1_hash, DICTIONARY_REVOCATION_LITS),
+            contract_key(&builder, v2_hash, DICTIONARY_REVOCATION_LITS)
+        );
+        let encoded_list: String = call_view(
+            &mut builder,
+            sender,
+            v2_hash,
+            "get_encoded_list",
+            runtime_args! {"id" => LIST_ID},
+            [3u8; 32],
         );
-        assert!(result_of_query.is_err());
+        assert_eq!(encoded_list, LIST_WITH_3214_REVOKED);
 
-        // deploy the contract.
-        builder.exec(execute_request).commit().expect_success();
+        call(
+            &mut builder,
+            sender,
+            v2_hash,
+            "reset",
+            runtime_args! {"id" => LIST_ID, "idx" => 3214u64},
+        )
+        .expect_success();
+        let rl = get_list(&builder, v2_hash, LIST_ID);
+        assert_eq!(rl.encoded_list(), EMPTY_LIST);
+        assert_eq!(rl.creator, sender);
 
-        // make assertions
-        let result_of_query = builder
-            .query(None, Key::Account(account_addr), &[KEY.to_string()])
-            .expect("should be stored value.")
+        // the team purse and the events are carried over as well
+        assert_eq!(
+            contract_key(&builder, v1_hash, PROTOCOL_TEAM_ACCOUNT),
+            contract_key(&builder, v2_hash, PROTOCOL_TEAM_ACCOUNT)
+        );
+        assert_eq!(events_count(&builder, v2_hash), 3);
+
+        // upgrading again disables the second version, the package calls the latest one
+        deploy(&mut builder, [4u8; 32]);
+        call_version(
+            &mut builder,
+            sender,
+            Some(2),
+            "revoke",
+            runtime_args! {"id" => LIST_ID, "idx" => 1u64},
+        )
+        .expect_failure();
+        call_version(
+            &mut builder,
+            sender,
+            None,
+            "revoke",
+            runtime_args! {"id" => LIST_ID, "idx" => 1u64},
+        )
+        .expect_success();
+        assert!(get_list(&builder, v2_hash, LIST_ID).is_revoked(1).unwrap());
+    }
+
+    #[test]
+    fn should_migrate_the_single_admin_on_upgrade() {
+        let mut builder = InMemoryWasmTestBuilder::default();
+        builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST).commit();
+        let admin = *DEFAULT_ACCOUNT_ADDR;
+        fund_account(&mut builder, OTHER_ACCOUNT_ADDR);
+
+        let v1_hash = deploy_wasm(&mut builder, fixture(SINGLE_ADMIN_FIXTURE), [1u8; 32]);
+        assert!(builder
+            .get_contract(v1_hash)
+            .expect("should have contract")
+            .named_keys()
+            .contains_key(ADMIN));
+
+        // the upgrade moves the admin and creates the configuration keys
+        let v2_hash = deploy(&mut builder, [2u8; 32]);
+        let named_keys = builder
+            .get_contract(v2_hash)
+            .expect("should have contract")
+            .named_keys()
+            .clone();
+        assert!(!named_keys.contains_key(ADMIN));
+        let admins: Vec<AccountHash> = builder
+            .query(None, named_keys[ADMINS], &[])
+            .expect("should have the admins")
             .as_cl_value()
-            .expect("should be cl value.")
+            .expect("should be cl value")
             .clone()
-            .into_t::<String>()
-            .expect("should be string.");
+            .into_t()
+            .expect("should be a list of accounts");
+        assert_eq!(admins, vec![admin]);
+
+        // the admin configures the upgraded contract
+        call(
+            &mut builder,
+            admin,
+            v2_hash,
+            "set_default_list_size",
+            runtime_args! {"size" => 4u32},
+        )
+        .expect_success();
+        call(
+            &mut builder,
+            OTHER_ACCOUNT_ADDR,
+            v2_hash,
+            "add_list",
+            runtime_args! {"id" => OTHER_LIST_ID},
+        )
+        .expect_success();
+        assert_eq!(get_list(&builder, v2_hash, OTHER_LIST_ID).size(), 4);
+        call(
+            &mut builder,
+            admin,
+            v2_hash,
+            "set_paused",
+            runtime_args! {"paused" => true},
+        )
+        .expect_success();
+        call(
+            &mut builder,
+            OTHER_ACCOUNT_ADDR,
+            v2_hash,
+            "revoke",
+            runtime_args! {"id" => OTHER_LIST_ID, "idx" => 1u64},
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_PAUSED);
 
-        assert_eq!(result_of_query, VALUE);
+        // the migration cannot be run again to take over the admins
+        call(
+            &mut builder,
+            OTHER_ACCOUNT_ADDR,
+            v2_hash,
+            "migrate",
+            runtime_args! {},
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_NOT_ADMIN);
     }
 
     #[test]
-    fn should_error_on_missing_runtime_arg() {
-        let secret_key = SecretKey::ed25519_from_bytes(MY_ACCOUNT).unwrap();
-        let public_key = PublicKey::from(&secret_key);
-        let account_addr = AccountHash::from(&public_key);
+    fn should_keep_the_lists_stored_under_raw_ids_on_upgrade() {
+        let mut builder = InMemoryWasmTestBuilder::default();
+        builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST).commit();
+        let owner = *DEFAULT_ACCOUNT_ADDR;
+        fund_account(&mut builder, OTHER_ACCOUNT_ADDR);
 
-        let session_code = PathBuf::from(CONTRACT_WASM);
-        let session_args = RuntimeArgs::new();
+        // the single admin contract stores the lists under the raw ids
+        let v1_hash = deploy_wasm(&mut builder, fixture(SINGLE_ADMIN_FIXTURE), [1u8; 32]);
+        call(
+            &mut builder,
+            owner,
+            v1_hash,
+            "add_list",
+            runtime_args! {"id" => LIST_ID},
+        )
+        .expect_success();
+        call(
+            &mut builder,
+            owner,
+            v1_hash,
+            "revoke",
+            runtime_args! {"id" => LIST_ID, "idx" => 3214u64},
+        )
+        .expect_success();
+        assert!(!builder
+            .get_expected_account(owner)
+            .named_keys()
+            .contains_key(CONTRACT_RLS_UREF));
 
-        let deploy_item = DeployItemBuilder::new()
-            .with_empty_payment_bytes(runtime_args! {ARG_AMOUNT => *DEFAULT_PAYMENT})
-            .with_authorization_keys(&[account_addr])
-            .with_address(*DEFAULT_ACCOUNT_ADDR)
-            .with_session_code(session_code, session_args)
-            .build();
+        let v2_hash = deploy(&mut builder, [2u8; 32]);
+        let encoded_list: String = call_view(
+            &mut builder,
+            OTHER_ACCOUNT_ADDR,
+            v2_hash,
+            "get_encoded_list",
+            runtime_args! {"id" => LIST_ID},
+            [3u8; 32],
+        );
+        assert_eq!(encoded_list, LIST_WITH_3214_REVOKED);
 
-        let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
+        // the list cannot be registered again nor updated by someone else
+        call(
+            &mut builder,
+            OTHER_ACCOUNT_ADDR,
+            v2_hash,
+            "add_list",
+            runtime_args! {"id" => LIST_ID},
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_LIST_EXISTS);
+        call(
+            &mut builder,
+            OTHER_ACCOUNT_ADDR,
+            v2_hash,
+            "reset",
+            runtime_args! {"id" => LIST_ID, "idx" => 3214u64},
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_NOT_LIST_OWNER);
 
+        // the list moves to the hashed key, read from the global state, when it is updated
+        assert_eq!(query_encoded_list(&builder, owner, LIST_ID), None);
+        call(
+            &mut builder,
+            owner,
+            v2_hash,
+            "reset",
+            runtime_args! {"id" => LIST_ID, "idx" => 3214u64},
+        )
+        .expect_success();
+        assert_eq!(
+            query_encoded_list(&builder, owner, LIST_ID),
+            Some(EMPTY_LIST.to_string())
+        );
+        let encoded_list: String = call_view(
+            &mut builder,
+            owner,
+            v2_hash,
+            "get_encoded_list",
+            runtime_args! {"id" => LIST_ID},
+            [4u8; 32],
+        );
+        assert_eq!(encoded_list, EMPTY_LIST);
+    }
+
+    #[test]
+    fn should_not_read_the_hashed_key_of_a_list_as_a_raw_id() {
         let mut builder = InMemoryWasmTestBuilder::default();
         builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST).commit();
-        builder.exec(execute_request).commit().expect_failure();
+        let owner = *DEFAULT_ACCOUNT_ADDR;
+        fund_account(&mut builder, OTHER_ACCOUNT_ADDR);
+
+        // the upgraded contract still reads the lists stored under the raw ids
+        let v1_hash = deploy_wasm(&mut builder, fixture(SINGLE_ADMIN_FIXTURE), [1u8; 32]);
+        call(
+            &mut builder,
+            owner,
+            v1_hash,
+            "add_list",
+            runtime_args! {"id" => LIST_ID},
+        )
+        .expect_success();
+        let v2_hash = deploy(&mut builder, [2u8; 32]);
+        call(
+            &mut builder,
+            owner,
+            v2_hash,
+            "add_list",
+            runtime_args! {"id" => OTHER_LIST_ID},
+        )
+        .expect_success();
+        call(
+            &mut builder,
+            owner,
+            v2_hash,
+            "revoke",
+            runtime_args! {"id" => OTHER_LIST_ID, "idx" => 3214u64},
+        )
+        .expect_success();
+
+        // the hashed key of a list is a valid id of another list
+        let hashed_id = dictionary_item_key(OTHER_LIST_ID);
+        call(
+            &mut builder,
+            owner,
+            v2_hash,
+            "is_revoked",
+            runtime_args! {"id" => hashed_id.clone(), "idx" => 3214u64},
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_LIST_NOT_FOUND);
+        call(
+            &mut builder,
+            owner,
+            v2_hash,
+            "update",
+            runtime_args! {
+                "id" => hashed_id.clone(),
+                "to_revoke" => vec![1u64],
+                "to_reset" => Vec::<u64>::new(),
+            },
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_LIST_NOT_FOUND);
+
+        call(
+            &mut builder,
+            OTHER_ACCOUNT_ADDR,
+            v2_hash,
+            "add_list",
+            runtime_args! {"id" => hashed_id.clone()},
+        )
+        .expect_success();
+        let encoded_list: String = call_view(
+            &mut builder,
+            owner,
+            v2_hash,
+            "get_encoded_list",
+            runtime_args! {"id" => hashed_id},
+            [3u8; 32],
+        );
+        assert_eq!(encoded_list, EMPTY_LIST);
+        let encoded_list: String = call_view(
+            &mut builder,
+            owner,
+            v2_hash,
+            "get_encoded_list",
+            runtime_args! {"id" => OTHER_LIST_ID},
+            [4u8; 32],
+        );
+        assert_eq!(encoded_list, LIST_WITH_3214_REVOKED);
     }
-}
 
-fn main() {
-    panic!("Execute \"cargo test\" to test the contract, not \"cargo run\".");
+    #[test]
+    fn should_create_the_registration_fee_on_upgrade() {
+        let mut builder = InMemoryWasmTestBuilder::default();
+        builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST).commit();
+        let admin = *DEFAULT_ACCOUNT_ADDR;
+        let fee = U512::from(1_000_000_000u64);
+        fund_account(&mut builder, OTHER_ACCOUNT_ADDR);
+
+        let v1_hash = deploy_wasm(&mut builder, fixture(WITHOUT_FEES_FIXTURE), [1u8; 32]);
+        assert!(!builder
+            .get_contract(v1_hash)
+            .expect("should have contract")
+            .named_keys()
+            .contains_key(REGISTRATION_FEE));
+
+        // the registration stays free after the upgrade
+        let v2_hash = deploy(&mut builder, [2u8; 32]);
+        call(
+            &mut builder,
+            OTHER_ACCOUNT_ADDR,
+            v2_hash,
+            "add_list",
+            runtime_args! {"id" => LIST_ID},
+        )
+        .expect_success();
+
+        // the upgrading account is the admin and sets a fee
+        call(
+            &mut builder,
+            admin,
+            v2_hash,
+            "set_registration_fee",
+            runtime_args! {"amount" => fee},
+        )
+        .expect_success();
+        add_list_with_fee(
+            &mut builder,
+            OTHER_ACCOUNT_ADDR,
+            v2_hash,
+            OTHER_LIST_ID,
+            fee,
+            [3u8; 32],
+        )
+        .expect_success();
+        let collected: U512 = call_view(
+            &mut builder,
+            admin,
+            v2_hash,
+            "get_fees_collected",
+            runtime_args! {},
+            [4u8; 32],
+        );
+        assert_eq!(collected, fee);
+    }
+
+    #[test]
+    fn should_emit_events() {
+        let (mut builder, contract_hash) = install();
+        let sender = *DEFAULT_ACCOUNT_ADDR;
+        assert_eq!(events_count(&builder, contract_hash), 0);
+
+        call(
+            &mut builder,
+            sender,
+            contract_hash,
+            "add_list",
+            runtime_args! {"id" => LIST_ID},
+        )
+        .expect_success();
+        call(
+            &mut builder,
+            sender,
+            contract_hash,
+            "revoke",
+            runtime_args! {"id" => LIST_ID, "idx" => 3214u64},
+        )
+        .expect_success();
+        call(
+            &mut builder,
+            sender,
+            contract_hash,
+            "reset",
+            runtime_args! {"id" => LIST_ID, "idx" => 3214u64},
+        )
+        .expect_success();
+        call(
+            &mut builder,
+            sender,
+            contract_hash,
+            "update",
+            runtime_args! {
+                "id" => LIST_ID,
+                "to_revoke" => vec![3214u64, 10],
+                "to_reset" => vec![10u64],
+            },
+        )
+        .expect_success();
+        // failed calls do not emit events
+        call(
+            &mut builder,
+            sender,
+            contract_hash,
+            "revoke",
+            runtime_args! {"id" => LIST_ID, "idx" => 131072u64},
+        )
+        .expect_failure();
+
+        assert_eq!(events_count(&builder, contract_hash), 4);
+        assert_eq!(
+            get_event::<ListAdded>(&builder, contract_hash, 0),
+            ListAdded {
+                id: LIST_ID.to_string(),
+                actor: sender,
+            }
+        );
+        assert_eq!(
+            get_event::<Revoked>(&builder, contract_hash, 1),
+            Revoked {
+                id: LIST_ID.to_string(),
+                actor: sender,
+                indices: vec![3214],
+            }
+        );
+        assert_eq!(
+            get_event::<Reset>(&builder, contract_hash, 2),
+            Reset {
+                id: LIST_ID.to_string(),
+                actor: sender,
+                indices: vec![3214],
+            }
+        );
+        assert_eq!(
+            get_event::<Updated>(&builder, contract_hash, 3),
+            Updated {
+                id: LIST_ID.to_string(),
+                actor: sender,
+                revoked: vec![3214, 10],
+                reset: vec![10],
+            }
+        );
+    }
+
+    #[test]
+    fn should_error_on_missing_list() {
+        let (mut builder, contract_hash) = install();
+
+        call(
+            &mut builder,
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            "revoke",
+            runtime_args! {"id" => LIST_ID, "idx" => 1u64},
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_LIST_NOT_FOUND);
+    }
+
+    #[test]
+    fn should_error_on_index_out_of_bounds() {
+        let (mut builder, contract_hash) = install();
+        let sender = *DEFAULT_ACCOUNT_ADDR;
+
+        call(
+            &mut builder,
+            sender,
+            contract_hash,
+            "add_list",
+            runtime_args! {"id" => LIST_ID},
+        )
+        .expect_success();
+
+        // a 16kb list holds 131072 elements
+        call(
+            &mut builder,
+            sender,
+            contract_hash,
+            "revoke",
+            runtime_args! {"id" => LIST_ID, "idx" => 131072u64},
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_INDEX_OUT_OF_BOUNDS);
+    }
+
+    #[test]
+    fn should_restrict_mutations_to_the_list_owner() {
+        let (mut builder, contract_hash) = install();
+
+        fund_account(&mut builder, OTHER_ACCOUNT_ADDR);
+
+        call(
+            &mut builder,
+            *DEFAULT_ACCOUNT_ADDR,
+            contract_hash,
+            "add_list",
+            runtime_args! {"id" => LIST_ID},
+        )
+        .expect_success();
+
+        call(
+            &mut builder,
+            OTHER_ACCOUNT_ADDR,
+            contract_hash,
+            "revoke",
+            runtime_args! {"id" => LIST_ID, "idx" => 3214u64},
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_NOT_LIST_OWNER);
+
+        call(
+            &mut builder,
+            OTHER_ACCOUNT_ADDR,
+            contract_hash,
+            "reset",
+            runtime_args! {"id" => LIST_ID, "idx" => 3214u64},
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_NOT_LIST_OWNER);
+
+        call(
+            &mut builder,
+            OTHER_ACCOUNT_ADDR,
+            contract_hash,
+            "update",
+            runtime_args! {
+                "id" => LIST_ID,
+                "to_revoke" => vec![3214u64],
+                "to_reset" => Vec::<u64>::new(),
+            },
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_NOT_LIST_OWNER);
+
+        // the list is untouched and still readable by anyone
+        let rl = get_list(&builder, contract_hash, LIST_ID);
+        assert_eq!(rl.encoded_list(), EMPTY_LIST);
+        assert_eq!(rl.creator, *DEFAULT_ACCOUNT_ADDR);
+        let revoked: bool = call_view(
+            &mut builder,
+            OTHER_ACCOUNT_ADDR,
+            contract_hash,
+            "is_revoked",
+            runtime_args! {"id" => LIST_ID, "idx" => 3214u64},
+            [2u8; 32],
+        );
+        assert!(!revoked);
+    }
+
+    #[test]
+    fn should_collect_registration_fees() {
+        let (mut builder, contract_hash) = install();
+        let admin = *DEFAULT_ACCOUNT_ADDR;
+        let fee = U512::from(1_000_000_000u64);
+        fund_account(&mut builder, OTHER_ACCOUNT_ADDR);
+        let team_purse = *contract_key(&builder, contract_hash, PROTOCOL_TEAM_ACCOUNT)
+            .as_uref()
+            .expect("should be a uref");
+
+        // only the admin sets the fee
+        call(
+            &mut builder,
+            OTHER_ACCOUNT_ADDR,
+            contract_hash,
+            "set_registration_fee",
+            runtime_args! {"amount" => fee},
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_NOT_ADMIN);
+        call(
+            &mut builder,
+            admin,
+            contract_hash,
+            "set_registration_fee",
+            runtime_args! {"amount" => fee},
+        )
+        .expect_success();
+
+        // the fee must be paid from a purse
+        call(
+            &mut builder,
+            OTHER_ACCOUNT_ADDR,
+            contract_hash,
+            "add_list",
+            runtime_args! {"id" => LIST_ID},
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_MISSING_PAYMENT_PURSE);
+
+        // the purse must hold the whole fee
+        add_list_with_fee(
+            &mut builder,
+            OTHER_ACCOUNT_ADDR,
+            contract_hash,
+            LIST_ID,
+            fee - 1,
+            [2u8; 32],
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_INSUFFICIENT_PAYMENT);
+
+        let payer_balance = account_balance(&builder, OTHER_ACCOUNT_ADDR);
+        add_list_with_fee(
+            &mut builder,
+            OTHER_ACCOUNT_ADDR,
+            contract_hash,
+            LIST_ID,
+            fee,
+            [3u8; 32],
+        )
+        .expect_success();
+        assert_eq!(
+            account_balance(&builder, OTHER_ACCOUNT_ADDR),
+            payer_balance - fee - *DEFAULT_PAYMENT
+        );
+        assert_eq!(builder.get_purse_balance(team_purse), fee);
+        let collected: U512 = call_view(
+            &mut builder,
+            OTHER_ACCOUNT_ADDR,
+            contract_hash,
+            "get_fees_collected",
+            runtime_args! {},
+            [4u8; 32],
+        );
+        assert_eq!(collected, fee);
+
+        // only the admin withdraws the fees
+        call(
+            &mut builder,
+            OTHER_ACCOUNT_ADDR,
+            contract_hash,
+            "withdraw_fees",
+            runtime_args! {},
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_NOT_ADMIN);
+
+        // the amount cannot exceed the collected fees
+        call(
+            &mut builder,
+            admin,
+            contract_hash,
+            "withdraw_fees",
+            runtime_args! {"amount" => fee + 1},
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_WITHDRAWAL_FAILED);
+
+        // a part of the fees can be withdrawn, the deploy payment is charged in full
+        let part = fee / 4;
+        let admin_balance = account_balance(&builder, admin);
+        call(
+            &mut builder,
+            admin,
+            contract_hash,
+            "withdraw_fees",
+            runtime_args! {"amount" => part},
+        )
+        .expect_success();
+        assert_eq!(
+            account_balance(&builder, admin),
+            admin_balance + part - *DEFAULT_PAYMENT
+        );
+        let collected: U512 = call_view(
+            &mut builder,
+            admin,
+            contract_hash,
+            "get_fees_collected",
+            runtime_args! {},
+            [5u8; 32],
+        );
+        assert_eq!(collected, fee - part);
+
+        let admin_balance = account_balance(&builder, admin);
+        call(
+            &mut builder,
+            admin,
+            contract_hash,
+            "withdraw_fees",
+            runtime_args! {},
+        )
+        .expect_success();
+        assert_eq!(builder.get_purse_balance(team_purse), U512::zero());
+        assert_eq!(
+            account_balance(&builder, admin),
+            admin_balance + fee - part - *DEFAULT_PAYMENT
+        );
+    }
+
+    #[test]
+    fn should_manage_lists_from_two_accounts() {
+        let (mut builder, contract_hash) = install();
+        let alice = *DEFAULT_ACCOUNT_ADDR;
+        let bob = OTHER_ACCOUNT_ADDR;
+        fund_account(&mut builder, bob);
+
+        // the same operations replayed with the model used by the NEAR contract
+        let mut alice_rl = RL2020::new(alice, 16).unwrap();
+        let mut bob_rl = RL2020::new(bob, 16).unwrap();
+
+        for (sender, id) in [(alice, LIST_ID), (bob, OTHER_LIST_ID)] {
+            call(
+                &mut builder,
+                sender,
+                contract_hash,
+                "add_list",
+                runtime_args! {"id" => id},
+            )
+            .expect_success();
+        }
+
+        call(
+            &mut builder,
+            alice,
+            contract_hash,
+            "revoke",
+            runtime_args! {"id" => LIST_ID, "idx" => 3214u64},
+        )
+        .expect_success();
+        alice_rl.revoke(3214).unwrap();
+        call(
+            &mut builder,
+            alice,
+            contract_hash,
+            "update",
+            runtime_args! {
+                "id" => LIST_ID,
+                "to_revoke" => vec![1u64, 2, 3],
+                "to_reset" => vec![2u64],
+            },
+        )
+        .expect_success();
+        alice_rl.set_many(vec![1, 2, 3], vec![2]).unwrap();
+
+        for idx in [0u64, 131071] {
+            call(
+                &mut builder,
+                bob,
+                contract_hash,
+                "revoke",
+                runtime_args! {"id" => OTHER_LIST_ID, "idx" => idx},
+            )
+            .expect_success();
+            bob_rl.revoke(idx).unwrap();
+        }
+        call(
+            &mut builder,
+            bob,
+            contract_hash,
+            "reset",
+            runtime_args! {"id" => OTHER_LIST_ID, "idx" => 0u64},
+        )
+        .expect_success();
+        bob_rl.reset(0).unwrap();
+
+        // the accounts cannot change each other lists
+        for (sender, id) in [(bob, LIST_ID), (alice, OTHER_LIST_ID)] {
+            call(
+                &mut builder,
+                sender,
+                contract_hash,
+                "revoke",
+                runtime_args! {"id" => id, "idx" => 10u64},
+            )
+            .expect_failure();
+            assert_user_error(&builder, ERROR_NOT_LIST_OWNER);
+        }
+
+        // the stored lists match the reference ones
+        let stored_alice_rl = get_list(&builder, contract_hash, LIST_ID);
+        let stored_bob_rl = get_list(&builder, contract_hash, OTHER_LIST_ID);
+        assert_eq!(stored_alice_rl.encoded_list(), alice_rl.encoded_list());
+        assert_eq!(stored_bob_rl.encoded_list(), bob_rl.encoded_list());
+        assert_eq!(stored_alice_rl, alice_rl);
+        assert_eq!(stored_bob_rl, bob_rl);
+        assert_ne!(alice_rl.encoded_list(), EMPTY_LIST);
+        assert_ne!(bob_rl.encoded_list(), EMPTY_LIST);
+
+        let cases = [
+            (LIST_ID, 3214u64, true),
+            (LIST_ID, 1, true),
+            (LIST_ID, 2, false),
+            (LIST_ID, 3, true),
+            (OTHER_LIST_ID, 0, false),
+            (OTHER_LIST_ID, 131071, true),
+        ];
+        for (case, &(id, idx, revoked)) in cases.iter().enumerate() {
+            let rl = get_list(&builder, contract_hash, id);
+            assert_eq!(rl.is_revoked(idx).unwrap(), revoked, "{} at {}", id, idx);
+            // anyone can query the lists through the stored contract
+            for (n, &sender) in [alice, bob].iter().enumerate() {
+                let stored: bool = call_view(
+                    &mut builder,
+                    sender,
+                    contract_hash,
+                    "is_revoked",
+                    runtime_args! {"id" => id, "idx" => idx},
+                    [(10 + 2 * case + n) as u8; 32],
+                );
+                assert_eq!(stored, revoked, "{} at {}", id, idx);
+            }
+        }
+
+        // out of range queries are rejected
+        call(
+            &mut builder,
+            bob,
+            contract_hash,
+            "is_revoked",
+            runtime_args! {"id" => LIST_ID, "idx" => 131072u64},
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_INDEX_OUT_OF_BOUNDS);
+        call(
+            &mut builder,
+            bob,
+            contract_hash,
+            "get_encoded_list",
+            runtime_args! {"id" => "example/rl/missing"},
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_LIST_NOT_FOUND);
+    }
+
+    #[test]
+    fn should_batch_update_many_lists() {
+        let (mut builder, contract_hash) = install();
+        let alice = *DEFAULT_ACCOUNT_ADDR;
+        let bob = OTHER_ACCOUNT_ADDR;
+        fund_account(&mut builder, bob);
+
+        for (sender, id) in [
+            (alice, LIST_ID),
+            (alice, OTHER_LIST_ID),
+            (bob, "example/rl/3"),
+        ] {
+            call(
+                &mut builder,
+                sender,
+                contract_hash,
+                "add_list",
+                runtime_args! {"id" => id},
+            )
+            .expect_success();
+        }
+
+        let mut expected_rl = RL2020::new(alice, 16).unwrap();
+        expected_rl.set_many(vec![1, 2, 3], vec![2]).unwrap();
+
+        batch_update(
+            &mut builder,
+            alice,
+            contract_hash,
+            vec![
+                (LIST_ID.to_string(), vec![3214], vec![]),
+                (OTHER_LIST_ID.to_string(), vec![1, 2, 3], vec![2]),
+            ],
+            [2u8; 32],
+        )
+        .expect_success();
+        assert_eq!(
+            get_list(&builder, contract_hash, LIST_ID).encoded_list(),
+            LIST_WITH_3214_REVOKED
+        );
+        assert_eq!(
+            get_list(&builder, contract_hash, OTHER_LIST_ID),
+            expected_rl
+        );
+
+        // a failing update reverts the whole batch
+        batch_update(
+            &mut builder,
+            alice,
+            contract_hash,
+            vec![
+                (LIST_ID.to_string(), vec![], vec![3214]),
+                ("example/rl/3".to_string(), vec![1], vec![]),
+            ],
+            [3u8; 32],
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_NOT_LIST_OWNER);
+        batch_update(
+            &mut builder,
+            alice,
+            contract_hash,
+            vec![
+                (LIST_ID.to_string(), vec![], vec![3214]),
+                (OTHER_LIST_ID.to_string(), vec![131072], vec![]),
+            ],
+            [4u8; 32],
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_INDEX_OUT_OF_BOUNDS);
+
+        assert_eq!(
+            get_list(&builder, contract_hash, LIST_ID).encoded_list(),
+            LIST_WITH_3214_REVOKED
+        );
+        assert_eq!(
+            get_list(&builder, contract_hash, OTHER_LIST_ID),
+            expected_rl
+        );
+        assert_eq!(
+            get_list(&builder, contract_hash, "example/rl/3").encoded_list(),
+            EMPTY_LIST
+        );
+    }
+
+    #[test]
+    fn should_read_lists_from_global_state() {
+        let (mut builder, contract_hash) = install();
+        let sender = *DEFAULT_ACCOUNT_ADDR;
+        // ids longer than the 64 bytes allowed for dictionary item keys are fine
+        let long_id = format!("example.com/credentials/status/{}", "x".repeat(64));
+
+        for id in [LIST_ID, long_id.as_str()] {
+            call(
+                &mut builder,
+                sender,
+                contract_hash,
+                "add_list",
+                runtime_args! {"id" => id},
+            )
+            .expect_success();
+            call(
+                &mut builder,
+                sender,
+                contract_hash,
+                "revoke",
+                runtime_args! {"id" => id, "idx" => 3214u64},
+            )
+            .expect_success();
+            assert_eq!(
+                query_encoded_list(&builder, sender, id).as_deref(),
+                Some(LIST_WITH_3214_REVOKED)
+            );
+        }
+        assert_eq!(query_encoded_list(&builder, sender, OTHER_LIST_ID), None);
+
+        // the item keys are the blake2b-256 of the ids and the reference is read only
+        assert_eq!(
+            dictionary_item_key(LIST_ID),
+            "692e21e9e8e3d82852ac54b0b11d92f852206df855d1739cb3f2fbd88aeb8422"
+        );
+        let rls_uref = *builder
+            .get_expected_account(sender)
+            .named_keys()
+            .get(CONTRACT_RLS_UREF)
+            .expect("should have the rls uref key")
+            .as_uref()
+            .expect("should be a uref");
+        assert_eq!(rls_uref.access_rights(), AccessRights::READ);
+    }
+
+    #[test]
+    fn should_manage_admins_and_config() {
+        let (mut builder, contract_hash) = install();
+        let alice = *DEFAULT_ACCOUNT_ADDR;
+        let bob = OTHER_ACCOUNT_ADDR;
+        fund_account(&mut builder, bob);
+
+        // non admins are rejected
+        let admin_calls = [
+            ("add_admin", runtime_args! {"account" => bob}),
+            ("remove_admin", runtime_args! {"account" => alice}),
+            ("set_default_list_size", runtime_args! {"size" => 4u32}),
+            ("set_paused", runtime_args! {"paused" => true}),
+            (
+                "set_registration_fee",
+                runtime_args! {"amount" => U512::one()},
+            ),
+            ("withdraw_fees", runtime_args! {}),
+        ];
+        for (entry_point, args) in admin_calls {
+            call(&mut builder, bob, contract_hash, entry_point, args).expect_failure();
+            assert_user_error(&builder, ERROR_NOT_ADMIN);
+        }
+
+        // admins add other admins
+        call(
+            &mut builder,
+            alice,
+            contract_hash,
+            "add_admin",
+            runtime_args! {"account" => bob},
+        )
+        .expect_success();
+
+        // the default list size is configurable
+        call(
+            &mut builder,
+            bob,
+            contract_hash,
+            "set_default_list_size",
+            runtime_args! {"size" => 0u32},
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_INVALID_CONFIG);
+        call(
+            &mut builder,
+            bob,
+            contract_hash,
+            "set_default_list_size",
+            runtime_args! {"size" => 4u32},
+        )
+        .expect_success();
+        call(
+            &mut builder,
+            alice,
+            contract_hash,
+            "add_list",
+            runtime_args! {"id" => LIST_ID},
+        )
+        .expect_success();
+        assert_eq!(get_list(&builder, contract_hash, LIST_ID).size(), 4);
+
+        // the mutations can be paused
+        call(
+            &mut builder,
+            bob,
+            contract_hash,
+            "set_paused",
+            runtime_args! {"paused" => true},
+        )
+        .expect_success();
+        let mutations = [
+            ("add_list", runtime_args! {"id" => OTHER_LIST_ID}),
+            ("revoke", runtime_args! {"id" => LIST_ID, "idx" => 1u64}),
+            ("reset", runtime_args! {"id" => LIST_ID, "idx" => 1u64}),
+            (
+                "update",
+                runtime_args! {
+                    "id" => LIST_ID,
+                    "to_revoke" => vec![1u64],
+                    "to_reset" => Vec::<u64>::new(),
+                },
+            ),
+        ];
+        for (entry_point, args) in mutations {
+            call(&mut builder, alice, contract_hash, entry_point, args).expect_failure();
+            assert_user_error(&builder, ERROR_PAUSED);
+        }
+        let revoked: bool = call_view(
+            &mut builder,
+            alice,
+            contract_hash,
+            "is_revoked",
+            runtime_args! {"id" => LIST_ID, "idx" => 1u64},
+            [2u8; 32],
+        );
+        assert!(!revoked);
+        call(
+            &mut builder,
+            alice,
+            contract_hash,
+            "set_paused",
+            runtime_args! {"paused" => false},
+        )
+        .expect_success();
+        call(
+            &mut builder,
+            alice,
+            contract_hash,
+            "revoke",
+            runtime_args! {"id" => LIST_ID, "idx" => 1u64},
+        )
+        .expect_success();
+
+        // removed admins are rejected, and the last admin cannot be removed
+        call(
+            &mut builder,
+            alice,
+            contract_hash,
+            "remove_admin",
+            runtime_args! {"account" => bob},
+        )
+        .expect_success();
+        call(
+            &mut builder,
+            bob,
+            contract_hash,
+            "set_paused",
+            runtime_args! {"paused" => true},
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_NOT_ADMIN);
+        call(
+            &mut builder,
+            alice,
+            contract_hash,
+            "remove_admin",
+            runtime_args! {"account" => alice},
+        )
+        .expect_failure();
+        assert_user_error(&builder, ERROR_LAST_ADMIN);
+    }
 }
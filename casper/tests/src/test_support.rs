@@ -0,0 +1,370 @@
+//! Helpers to install and drive the contract with the `InMemoryWasmTestBuilder`.
+use std::path::PathBuf;
+
+use casper_engine_test_support::{
+    DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, ARG_AMOUNT,
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
+    MINIMUM_ACCOUNT_CREATION_BALANCE,
+};
+use casper_event_standard::{EVENTS_DICT, EVENTS_LENGTH};
+use casper_execution_engine::core::{engine_state, execution};
+use casper_types::{
+    account::{blake2b, AccountHash},
+    bytesrepr::{Bytes, FromBytes, ToBytes},
+    runtime_args, ApiError, CLTyped, ContractHash, ContractPackageHash, ContractVersion, Key,
+    RuntimeArgs, URef, U512,
+};
+
+/// The lists are stored with the same model used by the NEAR contract.
+pub type RL2020 = rl2020_core::RL2020<AccountHash>;
+
+// Define the constants to match those in the contract.
+pub const CONTRACT_PACKAGE_HASH: &str = "rl2020_package_hash";
+pub const CONTRACT_HASH: &str = "rl2020_contract_hash";
+pub const CONTRACT_VERSION: &str = "rl2020_contract_version";
+pub const CONTRACT_RLS_UREF: &str = "rl2020_rls_uref";
+pub const DICTIONARY_REVOCATION_LITS: &str = "rls";
+pub const DICTIONARY_LEGACY_REVOCATION_LISTS: &str = "legacy_rls";
+pub const PROTOCOL_TEAM_ACCOUNT: &str = "team_account";
+pub const ADMIN: &str = "admin";
+pub const ADMINS: &str = "admins";
+pub const REGISTRATION_FEE: &str = "registration_fee";
+pub const CONTRACT_WASM: &str = "contract.wasm";
+pub const ADD_LIST_WASM: &str = "add_list.wasm";
+pub const BATCH_UPDATE_WASM: &str = "batch_update.wasm";
+pub const CALL_VIEW_WASM: &str = "call_view.wasm";
+/// The contract of the versions without admins and registration fees, built by
+/// `make fixtures` from the revision a214f7057326f7af51fab2feeaca768029576cf3.
+pub const WITHOUT_FEES_FIXTURE: &str = "contract_without_fees.wasm";
+/// The contract of the versions with a single `admin`, storing the lists under their raw ids,
+/// built by `make fixtures` from the revision 712d82e81f1874593ad8b62fa0f3ce05a279eed5.
+pub const SINGLE_ADMIN_FIXTURE: &str = "contract_single_admin.wasm";
+pub const VIEW_RESULT: &str = "view_result";
+
+/// Runs the contract session from the default account, installing the contract the first
+/// time and upgrading it afterwards, and returns the hash of the latest contract version.
+/// Every deploy needs its own hash, as the hashes of the stored values are derived from it.
+pub fn deploy(builder: &mut InMemoryWasmTestBuilder, deploy_hash: [u8; 32]) -> ContractHash {
+    // The test framework checks for compiled Wasm files in '<current working dir>/wasm'.  Paths
+    // relative to the current working dir (e.g. 'wasm/contract.wasm') can also be used, as can
+    // absolute paths.
+    deploy_wasm(builder, PathBuf::from(CONTRACT_WASM), deploy_hash)
+}
+
+/// Returns the path of a contract built from a previous version, kept to test the upgrades.
+pub fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join(name)
+}
+
+/// Runs the given contract session from the default account, see `deploy`.
+pub fn deploy_wasm(
+    builder: &mut InMemoryWasmTestBuilder,
+    wasm: PathBuf,
+    deploy_hash: [u8; 32],
+) -> ContractHash {
+    let deploy_item = DeployItemBuilder::new()
+        .with_empty_payment_bytes(runtime_args! {
+            ARG_AMOUNT => *DEFAULT_PAYMENT
+        })
+        .with_session_code(wasm, runtime_args! {})
+        .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
+        .with_address(*DEFAULT_ACCOUNT_ADDR)
+        .with_deploy_hash(deploy_hash)
+        .build();
+
+    let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
+    builder.exec(execute_request).commit().expect_success();
+
+    builder
+        .get_expected_account(*DEFAULT_ACCOUNT_ADDR)
+        .named_keys()
+        .get(CONTRACT_HASH)
+        .expect("should have contract hash key")
+        .into_hash()
+        .map(ContractHash::new)
+        .expect("should be a hash")
+}
+
+/// Installs the contract from the default account and returns the builder and the contract hash.
+pub fn install() -> (InMemoryWasmTestBuilder, ContractHash) {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST).commit();
+
+    let contract_hash = deploy(&mut builder, [1u8; 32]);
+    (builder, contract_hash)
+}
+
+/// Creates and funds an account from the default account.
+pub fn fund_account(builder: &mut InMemoryWasmTestBuilder, account: AccountHash) {
+    let transfer = ExecuteRequestBuilder::transfer(
+        *DEFAULT_ACCOUNT_ADDR,
+        runtime_args! {
+            "target" => account,
+            "amount" => U512::from(MINIMUM_ACCOUNT_CREATION_BALANCE),
+            "id" => Option::<u64>::None,
+        },
+    )
+    .build();
+    builder.exec(transfer).commit().expect_success();
+}
+
+/// Returns the balance of the main purse of an account.
+pub fn account_balance(builder: &InMemoryWasmTestBuilder, account: AccountHash) -> U512 {
+    builder.get_purse_balance(builder.get_expected_account(account).main_purse())
+}
+
+/// Registers a list through the session code paying the registration fee.
+pub fn add_list_with_fee<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    sender: AccountHash,
+    contract_hash: ContractHash,
+    id: &str,
+    amount: U512,
+    deploy_hash: [u8; 32],
+) -> &'a mut InMemoryWasmTestBuilder {
+    let deploy_item = DeployItemBuilder::new()
+        .with_empty_payment_bytes(runtime_args! {
+            ARG_AMOUNT => *DEFAULT_PAYMENT
+        })
+        .with_session_code(
+            PathBuf::from(ADD_LIST_WASM),
+            runtime_args! {
+                "contract_hash" => contract_hash,
+                "id" => id,
+                "amount" => amount,
+            },
+        )
+        .with_authorization_keys(&[sender])
+        .with_address(sender)
+        .with_deploy_hash(deploy_hash)
+        .build();
+
+    let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
+    builder.exec(execute_request).commit()
+}
+
+/// Updates several lists in one deploy through the batch session code.
+pub fn batch_update(
+    builder: &mut InMemoryWasmTestBuilder,
+    sender: AccountHash,
+    contract_hash: ContractHash,
+    updates: Vec<(String, Vec<u64>, Vec<u64>)>,
+    deploy_hash: [u8; 32],
+) -> &mut InMemoryWasmTestBuilder {
+    let deploy_item = DeployItemBuilder::new()
+        .with_empty_payment_bytes(runtime_args! {
+            ARG_AMOUNT => *DEFAULT_PAYMENT
+        })
+        .with_session_code(
+            PathBuf::from(BATCH_UPDATE_WASM),
+            runtime_args! {
+                "contract_hash" => contract_hash,
+                "updates" => updates,
+            },
+        )
+        .with_authorization_keys(&[sender])
+        .with_address(sender)
+        .with_deploy_hash(deploy_hash)
+        .build();
+
+    let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
+    builder.exec(execute_request).commit()
+}
+
+/// Calls an entry point of the installed contract from the given account.
+pub fn call<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    sender: AccountHash,
+    contract_hash: ContractHash,
+    entry_point: &str,
+    args: RuntimeArgs,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let request =
+        ExecuteRequestBuilder::contract_call_by_hash(sender, contract_hash, entry_point, args)
+            .build();
+    builder.exec(request).commit()
+}
+
+/// Calls an entry point of a version of the contract package, or of the latest enabled one
+/// if no version is given.
+pub fn call_version<'a>(
+    builder: &'a mut InMemoryWasmTestBuilder,
+    sender: AccountHash,
+    version: Option<ContractVersion>,
+    entry_point: &str,
+    args: RuntimeArgs,
+) -> &'a mut InMemoryWasmTestBuilder {
+    let package_hash = builder
+        .get_expected_account(*DEFAULT_ACCOUNT_ADDR)
+        .named_keys()
+        .get(CONTRACT_PACKAGE_HASH)
+        .expect("should have the package hash key")
+        .into_hash()
+        .map(ContractPackageHash::new)
+        .expect("should be a hash");
+    let request = ExecuteRequestBuilder::versioned_contract_call_by_hash(
+        sender,
+        package_hash,
+        version,
+        entry_point,
+        args,
+    )
+    .build();
+    builder.exec(request).commit()
+}
+
+/// Calls a view entry point through the `call_view` session code and returns the value
+/// it stored in the named keys of the sender.
+pub fn call_view<T: CLTyped + FromBytes>(
+    builder: &mut InMemoryWasmTestBuilder,
+    sender: AccountHash,
+    contract_hash: ContractHash,
+    entry_point: &str,
+    args: RuntimeArgs,
+    deploy_hash: [u8; 32],
+) -> T {
+    let deploy_item = DeployItemBuilder::new()
+        .with_empty_payment_bytes(runtime_args! {
+            ARG_AMOUNT => *DEFAULT_PAYMENT
+        })
+        .with_session_code(
+            PathBuf::from(CALL_VIEW_WASM),
+            runtime_args! {
+                "contract_hash" => contract_hash,
+                "entry_point" => entry_point,
+                "args" => Bytes::from(args.to_bytes().expect("should serialize the args")),
+            },
+        )
+        .with_authorization_keys(&[sender])
+        .with_address(sender)
+        .with_deploy_hash(deploy_hash)
+        .build();
+
+    let execute_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
+    builder.exec(execute_request).commit().expect_success();
+
+    builder
+        .query(None, Key::Account(sender), &[VIEW_RESULT.to_string()])
+        .expect("should have the view result")
+        .as_cl_value()
+        .expect("should be cl value")
+        .clone()
+        .into_t()
+        .expect("should have the type returned by the entry point")
+}
+
+/// Asserts that the last call reverted with the given contract error code.
+pub fn assert_user_error(builder: &InMemoryWasmTestBuilder, code: u16) {
+    match builder.get_error() {
+        Some(engine_state::Error::Exec(execution::Error::Revert(error))) => {
+            assert_eq!(error, ApiError::User(code))
+        }
+        error => panic!("expected user error {}, got {:?}", code, error),
+    }
+}
+
+/// Returns a named key of the contract.
+pub fn contract_key(
+    builder: &InMemoryWasmTestBuilder,
+    contract_hash: ContractHash,
+    name: &str,
+) -> Key {
+    *builder
+        .get_contract(contract_hash)
+        .expect("should have contract")
+        .named_keys()
+        .get(name)
+        .expect("should have the named key")
+}
+
+/// Returns the number of events emitted by the contract.
+pub fn events_count(builder: &InMemoryWasmTestBuilder, contract_hash: ContractHash) -> u32 {
+    builder
+        .query(
+            None,
+            contract_key(builder, contract_hash, EVENTS_LENGTH),
+            &[],
+        )
+        .expect("should have the events length")
+        .as_cl_value()
+        .expect("should be cl value")
+        .clone()
+        .into_t()
+        .expect("should be u32")
+}
+
+/// Reads and decodes the event at the given position.
+pub fn get_event<T: FromBytes>(
+    builder: &InMemoryWasmTestBuilder,
+    contract_hash: ContractHash,
+    index: u32,
+) -> T {
+    let events_uref = *contract_key(builder, contract_hash, EVENTS_DICT)
+        .as_uref()
+        .expect("should be a uref");
+    let bytes: Bytes = builder
+        .query_dictionary_item(None, events_uref, &index.to_string())
+        .expect("should have the event")
+        .as_cl_value()
+        .expect("should be cl value")
+        .clone()
+        .into_t()
+        .expect("should be bytes");
+    T::from_bytes(&bytes).expect("should be the event").0
+}
+
+/// Reads a revocation list straight from the contract dictionary.
+pub fn get_list(
+    builder: &InMemoryWasmTestBuilder,
+    contract_hash: ContractHash,
+    id: &str,
+) -> RL2020 {
+    let rls_uref: URef = *contract_key(builder, contract_hash, DICTIONARY_REVOCATION_LITS)
+        .as_uref()
+        .expect("should be a uref");
+
+    builder
+        .query_dictionary_item(None, rls_uref, &dictionary_item_key(id))
+        .expect("should have the list")
+        .as_cl_value()
+        .expect("should be cl value")
+        .clone()
+        .into_t::<RL2020>()
+        .expect("should be a list")
+}
+
+/// Returns the dictionary item key of a list, the hex encoded blake2b hash of the list id.
+pub fn dictionary_item_key(id: &str) -> String {
+    blake2b(id).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Reads the encoded list the way a client does with `state_get_dictionary_item`, using the
+/// read only dictionary reference stored in the named keys of the installing account.
+/// Returns `None` if the list does not exist.
+pub fn query_encoded_list(
+    builder: &InMemoryWasmTestBuilder,
+    account: AccountHash,
+    id: &str,
+) -> Option<String> {
+    let rls_uref: URef = *builder
+        .get_expected_account(account)
+        .named_keys()
+        .get(CONTRACT_RLS_UREF)
+        .expect("should have the rls uref key")
+        .as_uref()
+        .expect("should be a uref");
+    let dictionary_key = Key::dictionary(rls_uref, dictionary_item_key(id).as_bytes());
+
+    // the stored list is a (encoded list, creator, size) tuple
+    let (encoded_list, _, _) = builder
+        .query(None, dictionary_key, &[])
+        .ok()?
+        .as_cl_value()
+        .expect("should be cl value")
+        .clone()
+        .into_t::<(String, AccountHash, u32)>()
+        .expect("should be a list");
+    Some(encoded_list)
+}
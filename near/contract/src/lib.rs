@@ -1,80 +1,301 @@
+// revoke_signed takes all the fields of the signed message, the lint is raised on the
+// functions generated by near_bindgen that cannot be annotated
+#![allow(clippy::too_many_arguments)]
 mod models;
+#[allow(dead_code)]
 mod utils;
 
-use crate::{models::RL2020, utils::AccountId};
+use crate::models::{
+    Config, Issuer, ListDiff, ListEncoding, ListInfo, ListMetadata, PanicOnError, RLError,
+    SignedUpdate, Timestamps, RL2020,
+};
+use crate::utils::{verify_ed25519, AccountId, Near};
 
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::collections::LookupMap;
-use near_sdk::PanicOnDefault;
+use near_sdk::json_types::{U128, U64};
 #[allow(unused_imports)]
-use near_sdk::{env, near_bindgen, PromiseIndex};
+use near_sdk::{env, near_bindgen, Promise, PromiseIndex};
+use near_sdk::{PanicOnDefault, PublicKey};
+use rl2020_core::{validate_list_id, ListStore};
 
 #[near_bindgen]
 #[derive(PanicOnDefault, BorshDeserialize, BorshSerialize)]
 pub struct Contract {
     owner: AccountId,
-    rls: LookupMap<String, (String, RL2020)>,
+    config: Config,
+    rls: LookupMap<String, RL2020>,
+    // when set, all the mutations are rejected
+    paused: bool,
+    // ids of the lists whose mutations are rejected, with whether the pause was set by the
+    // contract owner, in which case only the contract owner can resume the list
+    paused_rls: LookupMap<String, bool>,
+    // keys allowed to sign the updates of a list, relayed with revoke_signed
+    signers: LookupMap<String, PublicKey>,
+    // last nonce used by the signed updates of a list
+    nonces: LookupMap<String, u64>,
+    // issuer identities recorded when the lists are registered
+    issuers: LookupMap<String, Issuer>,
+    // descriptive settings of the lists
+    metadata: LookupMap<String, ListMetadata>,
+    // registration and last change timestamps of the lists
+    timestamps: LookupMap<String, Timestamps>,
+    // registration deposits (in yoctoNEAR) not withdrawn by the contract owner
+    fees_collected: u128,
 }
 
 #[near_bindgen]
 impl Contract {
     #[init]
-    pub fn new() -> Self {
+    pub fn new(owner: AccountId, config: Option<Config>) -> Self {
+        let config = config.unwrap_or_default();
+        config.validate().unwrap_or_else(|e| e.panic());
         Self {
-            owner: AccountId::from("metadid.testnet"),
+            owner,
+            config,
             rls: LookupMap::new(b"r"),
+            paused: false,
+            paused_rls: LookupMap::new(b"p"),
+            signers: LookupMap::new(b"s"),
+            nonces: LookupMap::new(b"n"),
+            issuers: LookupMap::new(b"i"),
+            metadata: LookupMap::new(b"m"),
+            timestamps: LookupMap::new(b"t"),
+            fees_collected: 0,
         }
     }
 
-    /// register a new revocation list
-    pub fn register_list(&mut self, id: String) {
-        if id.trim().is_empty() {
-            env::panic_str("ERR_INVALID_RL_LIST");
+    pub fn get_owner(&self) -> AccountId {
+        self.owner.clone()
+    }
+
+    /// transfer the ownership of the contract to a new account
+    pub fn set_owner(&mut self, owner: AccountId) {
+        self.assert_owner();
+        self.owner = owner;
+        env::log_str("contract owner updated");
+    }
+
+    pub fn get_config(&self) -> Config {
+        self.config.clone()
+    }
+
+    /// replace the contract-wide settings
+    /// the new settings apply only to lists registered afterwards
+    pub fn set_config(&mut self, config: Config) {
+        self.assert_owner();
+        config.validate().unwrap_or_else(|e| e.panic());
+        self.config = config;
+        env::log_str("contract config updated");
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// pause or resume all the mutations on the contract (owner only)
+    /// views are not affected
+    pub fn set_paused(&mut self, paused: bool) {
+        self.assert_owner();
+        self.paused = paused;
+        env::log_str(match paused {
+            true => "contract paused",
+            false => "contract resumed",
+        });
+    }
+
+    /// get the registration deposits (in yoctoNEAR) that can be withdrawn by the contract owner
+    pub fn get_fees_collected(&self) -> U128 {
+        U128(self.fees_collected)
+    }
+
+    /// transfer the collected registration deposits to the contract owner (owner only),
+    /// all of them unless an amount is given
+    pub fn withdraw_fees(&mut self, amount: Option<U128>) -> Promise {
+        self.assert_owner();
+        let amount = amount.map_or(self.fees_collected, |a| a.0);
+        if amount > self.fees_collected {
+            RLError::InsufficientFees {
+                requested: amount,
+                available: self.fees_collected,
+            }
+            .panic();
+        }
+        self.fees_collected -= amount;
+        env::log_str("registration fees withdrawn");
+        Promise::new(env::predecessor_account_id()).transfer(amount)
+    }
+
+    pub fn is_list_paused(&self, id: String) -> bool {
+        self.paused_rls.contains_key(&id)
+    }
+
+    /// pause or resume the mutations on a single list
+    /// it can be called by the contract owner or by the list owner, a list paused by the
+    /// contract owner can be resumed only by the contract owner
+    pub fn set_list_paused(&mut self, id: String, paused: bool) {
+        let is_owner = env::predecessor_account_id().to_string() == self.owner;
+        match is_owner {
+            true => self.load_list(&id),
+            false => self.load_owned_list(&id),
         }
-        if self.rls.contains_key(&id) {
-            env::panic_str("ERR_RL_EXISTS");
+        .unwrap_or_else(|e| e.panic());
+        let paused_by_owner = self.paused_rls.get(&id).unwrap_or(false);
+        if paused_by_owner && !is_owner {
+            RLError::Unauthorized.panic();
         }
-        let rl = RL2020::new().unwrap_or_else(|e| env::panic_str(&e.message));
+        match paused {
+            true => self.paused_rls.insert(&id, &(is_owner || paused_by_owner)),
+            false => self.paused_rls.remove(&id),
+        };
+        env::log_str(match paused {
+            true => "revocation list paused",
+            false => "revocation list resumed",
+        });
+    }
 
-        let owner = env::predecessor_account_id().to_string();
-        self.rls.insert(&id, &(owner, rl));
+    /// register a new revocation list
+    /// size is the size of the list in kilobytes, if omitted the configured default is used
+    /// issuer is the DID and verification method of the issuer publishing the list
+    /// metadata is the description, URL and status purpose of the list
+    /// the list is registered in the namespace of the caller as `<caller>:<id>`, the returned
+    /// id used by all the other methods, so that an account cannot take the ids of another
+    #[payable]
+    pub fn register_list(
+        &mut self,
+        id: String,
+        size: Option<usize>,
+        issuer: Option<Issuer>,
+        metadata: Option<ListMetadata>,
+    ) -> String {
+        self.assert_not_paused(None);
+        let id = Self::namespaced_id(&id).unwrap_or_else(|e| e.panic());
+        if env::attached_deposit() < self.config.registration_fee.0 {
+            RLError::InsufficientDeposit {
+                required: self.config.registration_fee.0,
+                attached: env::attached_deposit(),
+            }
+            .panic();
+        }
+        let size = size.unwrap_or(self.config.default_list_size);
+        if size > self.config.max_list_size {
+            RLError::ListTooLarge {
+                size,
+                max: self.config.max_list_size,
+            }
+            .panic();
+        }
+        if let Some(issuer) = &issuer {
+            issuer.validate().unwrap_or_else(|e| e.panic());
+        }
+        if let Some(metadata) = &metadata {
+            metadata.validate().unwrap_or_else(|e| e.panic());
+        }
+        <Self as ListStore<Near>>::register_list(self, &id, size).unwrap_or_else(|e| e.panic());
+        if let Some(issuer) = &issuer {
+            self.issuers.insert(&id, issuer);
+        }
+        if let Some(metadata) = &metadata {
+            self.metadata.insert(&id, metadata);
+        }
+        self.fees_collected += env::attached_deposit();
         env::log_str("Added a new revocation list");
+        id
     }
 
-    pub fn get_encoded_list(&self, id: String) -> String {
-        let rl = self
-            .rls
+    /// get the issuer identity recorded for a list, the list credentials must use its DID
+    /// as `issuer` and be signed with its verification method
+    pub fn get_list_issuer(&self, id: String) -> Option<Issuer> {
+        self.load_list(&id).unwrap_or_else(|e| e.panic());
+        self.issuers.get(&id)
+    }
+
+    /// replace the description, URL, status purpose and encoding of a list (list owner only)
+    pub fn update_metadata(&mut self, id: String, metadata: ListMetadata) {
+        self.assert_not_paused(Some(&id));
+        self.load_owned_list(&id).unwrap_or_else(|e| e.panic());
+        metadata.validate().unwrap_or_else(|e| e.panic());
+        self.metadata.insert(&id, &metadata);
+        env::log_str("revocation list metadata updated");
+    }
+
+    /// get the description of a list: its owner, size, metadata, issuer and timestamps
+    pub fn get_list_info(&self, id: String) -> ListInfo {
+        let rl = self.load_list(&id).unwrap_or_else(|e| e.panic());
+        let metadata = self.metadata.get(&id).unwrap_or_default();
+        // put_list records the timestamps of every stored list
+        let timestamps = self
+            .timestamps
             .get(&id)
-            .unwrap_or_else(|| env::panic_str("ERR_RL_NOT_FOUND"));
+            .unwrap_or_else(|| RLError::NotFound.panic());
+        ListInfo {
+            creator: rl.creator.clone(),
+            size: rl.size(),
+            description: metadata.description,
+            url: metadata.url,
+            status_purpose: metadata.status_purpose,
+            encoding: metadata.encoding,
+            issuer: self.issuers.get(&id),
+            created_at: U64(timestamps.created_at),
+            updated_at: U64(timestamps.updated_at),
+            id,
+        }
+    }
 
-        rl.to_string()
+    /// get the list compressed with the encoding set in the list metadata, zlib by default
+    pub fn get_encoded_list(&self, id: String) -> String {
+        let rl = self.load_list(&id).unwrap_or_else(|e| e.panic());
+        match self.metadata.get(&id).unwrap_or_default().encoding {
+            ListEncoding::Base64Zlib => rl.encoded_list(),
+            ListEncoding::Base64Gzip => rl.gzip_encoded_list().unwrap_or_else(|e| e.panic()),
+        }
+    }
+
+    /// get the indexes revoked and reset since a previous version of the list, given as
+    /// returned by get_encoded_list, so that a client can update its copy of the list
+    pub fn diff_encoded_list(&self, id: String, previous_encoded: String) -> ListDiff {
+        let rl = self.load_list(&id).unwrap_or_else(|e| e.panic());
+        rl.diff(&previous_encoded)
+            .unwrap_or_else(|e| e.panic())
+            .into()
     }
 
     pub fn is_revoked(&self, id: String, idx: u64) -> bool {
-        let rl = self
-            .rls
-            .get(&id)
-            .unwrap_or_else(|| env::panic_str("ERR_RL_NOT_FOUND"));
-        rl.get(idx).unwrap_or_else(|e| env::panic_str(&e.message))
+        let rl = self.load_list(&id).unwrap_or_else(|e| e.panic());
+        rl.get(idx).unwrap_or_else(|e| e.panic())
     }
 
-    fn check_permission((ref owner, _): &(String, RL2020)) {
-        if env::predecessor_account_id() != self.owner {
-            env::panic_str("ERR_NOT_AUTHORIZED");
+    fn assert_owner(&self) {
+        if env::predecessor_account_id().to_string() != self.owner {
+            RLError::Unauthorized.panic();
         }
     }
 
-    pub fn set_list(&mut self, id: String, hex_encoded_list: String) {
-        let mut rl = self.rls.get(&id).unwrap_or_else(|| {
-            env::panic_str("ERR_RL_NOT_FOUND");
-        });
-        Self::check_permission(&rl);
-        let encoded_list = hex::decode(hex_encoded_list).unwrap_or_else(|e| {
-            env::panic_str(&e.to_string());
-        });
-        rl.replace(encoded_list)
-            .unwrap_or_else(|e| env::panic_str(&e.message));
-        self.rls.insert(&id, &rl);
+    fn assert_not_paused(&self, id: Option<&String>) {
+        if self.paused || id.is_some_and(|id| self.paused_rls.contains_key(id)) {
+            RLError::Paused.panic();
+        }
+    }
+
+    /// replace the bitset of a list (list owner only)
+    /// the bitset is given either hex encoded (uncompressed) with hex_encoded_list or
+    /// compressed and base64 encoded, as returned by get_encoded_list, with encoded_list
+    /// and must have the size of the list,
+    /// if keep_revoked is true the new bitset cannot reset the revoked credentials
+    pub fn set_list(
+        &mut self,
+        id: String,
+        hex_encoded_list: Option<String>,
+        encoded_list: Option<String>,
+        keep_revoked: Option<bool>,
+    ) {
+        self.assert_not_paused(Some(&id));
+        let bit_set = self
+            .decode_set_list(&id, hex_encoded_list, encoded_list)
+            .unwrap_or_else(|e| e.panic());
+        self.replace_list(&id, bit_set, keep_revoked.unwrap_or(false))
+            .unwrap_or_else(|e| e.panic());
+        env::log_str("revocation list replaced");
     }
 
     /// revoke a credential
@@ -92,46 +313,204 @@ impl Contract {
     /// revoke is a list of ids to revoke
     /// reset take precedence over revoke
     pub fn update(&mut self, id: String, to_revoke: Vec<u64>, to_reset: Vec<u64>) {
-        let mut rl = self
-            .rls
-            .get(&id)
-            .unwrap_or_else(|| env::panic_str("ERR_RL_NOT_FOUND"));
-       
-        Self::check_permission(&rl);
+        self.assert_not_paused(Some(&id));
+        self.update_list(&id, to_revoke, to_reset)
+            .unwrap_or_else(|e| e.panic());
+        env::log_str("revocation list updated");
+    }
 
-        rl.set_many(to_revoke, to_reset)
-            .unwrap_or_else(|e| env::panic_str(&e.message));
+    pub fn get_list_signer(&self, id: String) -> Option<PublicKey> {
+        self.signers.get(&id)
+    }
 
-        self.rls.insert(&id, &rl);
-        env::log_str("revocation list updated");
+    /// get the last nonce used by the signed updates of a list, 0 if none
+    pub fn get_list_nonce(&self, id: String) -> U64 {
+        U64(self.nonces.get(&id).unwrap_or(0))
     }
 
-    fn set(&mut self, id: String, idx: u64, revoked: bool) {
-        let mut rl = self
-            .rls
-            .get(&id)
-            .unwrap_or_else(|| env::panic_str("ERR_RL_NOT_FOUND"));
-        let revoker = env::predecessor_account_id();
-        if rl.creator.to_string() != revoker.to_string() {
-            env::panic_str("ERR_UNAUTHORIZED");
+    /// register (or remove) the Ed25519 key allowed to sign the updates of a list (list owner only)
+    pub fn set_list_signer(&mut self, id: String, public_key: Option<PublicKey>) {
+        self.assert_not_paused(Some(&id));
+        self.load_owned_list(&id).unwrap_or_else(|e| e.panic());
+        match &public_key {
+            Some(public_key) => self.signers.insert(&id, public_key),
+            None => self.signers.remove(&id),
+        };
+        env::log_str("revocation list signer updated");
+    }
+
+    /// apply an update signed by the list signer, the transaction can be submitted by any account
+    /// the signature (hex encoded) is over the Borsh serialization of a `SignedUpdate` message,
+    /// the nonce must be greater than the last one used for the list and
+    /// expiry is the block timestamp (in nanoseconds) after which the message is rejected
+    pub fn revoke_signed(
+        &mut self,
+        id: String,
+        to_revoke: Vec<u64>,
+        to_reset: Vec<u64>,
+        nonce: U64,
+        expiry: U64,
+        public_key: PublicKey,
+        signature: String,
+    ) {
+        self.assert_not_paused(Some(&id));
+        let message = SignedUpdate {
+            contract: env::current_account_id().to_string(),
+            id,
+            to_revoke,
+            to_reset,
+            nonce: nonce.0,
+            expiry: expiry.0,
+        };
+        self.apply_signed_update(message, &public_key, &signature)
+            .unwrap_or_else(|e| e.panic());
+        env::log_str("revocation list updated with a signed update");
+    }
+
+    /// apply_signed_update checks the signer, expiry, nonce and signature of a signed update
+    /// and applies it to the list
+    fn apply_signed_update(
+        &mut self,
+        message: SignedUpdate,
+        public_key: &PublicKey,
+        signature: &str,
+    ) -> Result<(), RLError> {
+        let mut rl = self.load_list(&message.id)?;
+        if self.signers.get(&message.id).as_ref() != Some(public_key) {
+            return Err(RLError::Unauthorized);
+        }
+        if env::block_timestamp() > message.expiry {
+            return Err(RLError::Expired);
         }
+        let last = self.nonces.get(&message.id).unwrap_or(0);
+        if message.nonce <= last {
+            return Err(RLError::InvalidNonce {
+                last,
+                got: message.nonce,
+            });
+        }
+        let signature =
+            hex::decode(signature).map_err(|e| RLError::InvalidEncoding(e.to_string()))?;
+        let payload = message
+            .try_to_vec()
+            .map_err(|e| RLError::InvalidEncoding(e.to_string()))?;
+        verify_ed25519(public_key, &payload, &signature)?;
 
-        match revoked {
-            true => rl.set_many(vec![idx], vec![]),
-            false => rl.set_many(vec![], vec![idx]),
+        rl.set_many(message.to_revoke, message.to_reset)?;
+        self.put_list(&message.id, &rl);
+        self.nonces.insert(&message.id, &message.nonce);
+        Ok(())
+    }
+
+    /// decode_set_list decodes the bitset given to set_list, exactly one of the encodings
+    /// must be provided
+    fn decode_set_list(
+        &self,
+        id: &str,
+        hex_encoded_list: Option<String>,
+        encoded_list: Option<String>,
+    ) -> Result<Vec<u8>, RLError> {
+        match (hex_encoded_list, encoded_list) {
+            (Some(hex_encoded_list), None) => {
+                hex::decode(hex_encoded_list).map_err(|e| RLError::InvalidEncoding(e.to_string()))
+            }
+            (None, Some(encoded_list)) => self.load_list(id)?.decode_bit_set(&encoded_list),
+            _ => Err(RLError::InvalidEncoding(
+                "one of hex_encoded_list or encoded_list is required".to_string(),
+            )),
         }
-        .unwrap_or_else(|e| env::panic_str(&e.message));
+    }
+
+    /// namespaced_id returns the id of a list registered by the caller, both the id chosen by
+    /// the caller and the namespaced id must be valid list ids
+    fn namespaced_id(id: &str) -> Result<String, RLError> {
+        validate_list_id(id)?;
+        let id = format!("{}:{}", env::predecessor_account_id(), id);
+        validate_list_id(&id)?;
+        Ok(id)
+    }
 
-        self.rls.insert(&id, &rl);
+    fn set(&mut self, id: String, idx: u64, revoked: bool) {
+        self.assert_not_paused(Some(&id));
+        match revoked {
+            true => self.update_list(&id, vec![idx], vec![]),
+            false => self.update_list(&id, vec![], vec![idx]),
+        }
+        .unwrap_or_else(|e| e.panic());
         env::log_str("revocation list element updated");
     }
 }
 
+impl ListStore<Near> for Contract {
+    fn get_list(&self, id: &str) -> Option<RL2020> {
+        self.rls.get(&id.to_string())
+    }
+
+    /// the list is stored with the timestamp of the change, and of the registration if new
+    fn put_list(&mut self, id: &str, rl: &RL2020) {
+        let id = id.to_string();
+        let now = env::block_timestamp();
+        let timestamps = match self.timestamps.get(&id) {
+            Some(t) => Timestamps {
+                updated_at: now,
+                ..t
+            },
+            None => Timestamps {
+                created_at: now,
+                updated_at: now,
+            },
+        };
+        self.rls.insert(&id, rl);
+        self.timestamps.insert(&id, &timestamps);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::StatusPurpose;
     use near_sdk::test_utils::VMContextBuilder;
     use near_sdk::{testing_env, AccountId};
+    use rl2020_core::MAX_LIST_ID_LENGTH;
+
+    const SIGNER_SEED: [u8; 32] = [7; 32];
+
+    fn signer_public_key() -> PublicKey {
+        let key = ed25519_dalek::SigningKey::from_bytes(&SIGNER_SEED).verifying_key();
+        // the first byte is the ed25519 curve type
+        PublicKey::try_from([&[0u8][..], key.as_bytes()].concat()).unwrap()
+    }
+
+    /// signed_update returns the update and its hex encoded signature by the list signer
+    fn signed_update(
+        id: &str,
+        to_revoke: Vec<u64>,
+        nonce: u64,
+        expiry: u64,
+    ) -> (SignedUpdate, String) {
+        use ed25519_dalek::Signer;
+        let message = SignedUpdate {
+            contract: env::current_account_id().to_string(),
+            id: id.to_string(),
+            to_revoke,
+            to_reset: vec![],
+            nonce,
+            expiry,
+        };
+        let key = ed25519_dalek::SigningKey::from_bytes(&SIGNER_SEED);
+        let signature = hex::encode(key.sign(&message.try_to_vec().unwrap()).to_bytes());
+        (message, signature)
+    }
+
+    /// setup_signed_list registers a list owned by alice with the test signer
+    fn setup_signed_list() -> (Contract, String) {
+        let alice = AccountId::new_unchecked("alice.testnet".to_string());
+        testing_env!(get_context(alice.clone()).build());
+        let mut contract = Contract::new(alice.to_string(), None);
+        let id = contract.register_list("example/rl/1".to_string(), Some(16), None, None);
+        contract.set_list_signer(id.clone(), Some(signer_public_key()));
+        (contract, id)
+    }
 
     fn get_context(predecessor: AccountId) -> VMContextBuilder {
         let mut builder = VMContextBuilder::new();
@@ -147,12 +526,11 @@ mod tests {
 
         testing_env!(context.build());
 
-        let mut contract = Contract::new();
+        let mut contract = Contract::new(alice.to_string(), None);
 
-        contract.add_list("example/rl/1".to_string());
-        let result = contract
-            .get_encoded_list("example/rl/1".to_string())
-            .to_string();
+        let id = contract.register_list("example/rl/1".to_string(), Some(16), None, None);
+        assert_eq!(id, "alice.testnet:example/rl/1");
+        let result = contract.get_encoded_list(id).to_string();
 
         assert_eq!(
             &result,
@@ -160,6 +538,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_list_namespace() {
+        let alice = AccountId::new_unchecked("alice.testnet".to_string());
+        let bob = AccountId::new_unchecked("bob.testnet".to_string());
+        testing_env!(get_context(bob.clone()).build());
+        let mut contract = Contract::new(alice.to_string(), None);
+
+        // bob cannot take the id alice wants
+        let bob_id = contract.register_list("acme/employees".to_string(), None, None, None);
+        testing_env!(get_context(alice).build());
+        let alice_id = contract.register_list("acme/employees".to_string(), None, None, None);
+        assert_eq!(bob_id, "bob.testnet:acme/employees");
+        assert_eq!(alice_id, "alice.testnet:acme/employees");
+
+        // an id in the namespace of bob registered by alice stays in the namespace of alice
+        let id = contract.register_list("bob.testnet:acme/x".to_string(), None, None, None);
+        assert_eq!(id, "alice.testnet:bob.testnet:acme/x");
+
+        contract.revoke(alice_id.clone(), 1);
+        assert!(contract.is_revoked(alice_id, 1));
+        assert!(!contract.is_revoked(bob_id.clone(), 1));
+        assert_eq!(
+            contract.load_owned_list(&bob_id),
+            Err(RLError::Unauthorized)
+        );
+    }
+
+    #[test]
+    fn test_list_id() {
+        let alice = AccountId::new_unchecked("alice.testnet".to_string());
+        testing_env!(get_context(alice).build());
+
+        // the rules of `rl2020_core::validate_list_id`, the namespace takes 14 bytes
+        let max = "a".repeat(MAX_LIST_ID_LENGTH - "alice.testnet:".len());
+        let too_long = format!("{}a", max);
+        let table = [
+            ("acme/employees-2023_v1", true),
+            ("bob.testnet:rl/1", true),
+            (max.as_str(), true),
+            ("", false),
+            (too_long.as_str(), false),
+            (" rl/1", false),
+            ("rl/1 ", false),
+            ("rl/1\n", false),
+            ("rl/\u{e9}", false),
+            ("rl/e\u{301}", false),
+            ("rl//1", false),
+            ("/rl/1", false),
+            ("rl/1:", false),
+        ];
+        for (id, valid) in table {
+            let expected = match valid {
+                true => Ok(format!("alice.testnet:{}", id)),
+                false => Err(RLError::InvalidListId),
+            };
+            assert_eq!(Contract::namespaced_id(id), expected, "{:?}", id);
+        }
+    }
+
     #[test]
     fn test_revoke_reset() {
         let alice = AccountId::new_unchecked("alice.testnet".to_string());
@@ -168,18 +605,16 @@ mod tests {
 
         testing_env!(context.build());
 
-        let mut contract = Contract::new();
+        let mut contract = Contract::new(alice.to_string(), None);
 
-        let id = "example/rl/1";
-
-        contract.add_list(id.to_string());
+        let id = contract.register_list("example/rl/1".to_string(), Some(16), None, None);
 
         let idx = 3214;
         contract.revoke(id.to_string(), idx);
         let is_revoked = contract.is_revoked(id.to_string(), idx);
-        assert_eq!(is_revoked, true);
+        assert!(is_revoked);
 
-        let result = contract.get_encoded_list("example/rl/1".to_string());
+        let result = contract.get_encoded_list(id.to_string());
 
         assert_eq!(
             &result,
@@ -188,13 +623,619 @@ mod tests {
 
         contract.reset(id.to_string(), idx);
         let is_revoked = contract.is_revoked(id.to_string(), idx);
-        assert_eq!(is_revoked, false);
+        assert!(!is_revoked);
 
-        let result = contract.get_encoded_list("example/rl/1".to_string());
+        let result = contract.get_encoded_list(id.to_string());
 
         assert_eq!(
             &result,
             "eJztwDEBAAAAwqD1T20MHygAAAAAAAAAAAAAAAAAAADgbUAAAAE="
         );
     }
+
+    #[test]
+    fn test_set_list() {
+        let alice = AccountId::new_unchecked("alice.testnet".to_string());
+        testing_env!(get_context(alice.clone()).build());
+
+        let mut contract = Contract::new(alice.to_string(), None);
+        let id = contract.register_list("example/rl/1".to_string(), Some(16), None, None);
+        let other = contract.register_list("example/rl/2".to_string(), Some(16), None, None);
+        contract.revoke(other.clone(), 3214);
+
+        // the encoded list of a list can be uploaded to another list
+        let encoded_list = contract.get_encoded_list(other.clone());
+        contract.set_list(id.clone(), None, Some(encoded_list.clone()), Some(true));
+        assert_eq!(contract.get_encoded_list(id.clone()), encoded_list);
+
+        // or the uncompressed bitset, hex encoded
+        let mut bit_set = vec![0u8; 16 * 1024];
+        bit_set[3214 / 8] = 1 << (3214 % 8);
+        bit_set[0] = 1;
+        contract.set_list(id.clone(), Some(hex::encode(&bit_set)), None, Some(true));
+        assert!(contract.is_revoked(id.clone(), 0));
+        assert!(contract.is_revoked(id.clone(), 3214));
+
+        // the revoked credentials can be reset only when keep_revoked is not set
+        assert_eq!(
+            contract.replace_list(&id, vec![0; 16 * 1024], true),
+            Err(RLError::RevokedBitCleared { index: 0 })
+        );
+        contract.set_list(id.clone(), None, Some(encoded_list), None);
+        assert!(!contract.is_revoked(id.clone(), 0));
+
+        // the bitset must have the size of the list
+        let small = RL2020::new(alice.to_string(), 1).unwrap().encoded_list();
+        assert_eq!(
+            contract.decode_set_list(&id, None, Some(small)),
+            Err(RLError::InvalidSize {
+                expected: 16 * 1024,
+                got: 1024
+            })
+        );
+        assert!(matches!(
+            contract.decode_set_list(&id, None, None),
+            Err(RLError::InvalidEncoding(_))
+        ));
+        assert!(matches!(
+            contract.decode_set_list(&id, Some("00".to_string()), Some("AA==".to_string())),
+            Err(RLError::InvalidEncoding(_))
+        ));
+    }
+
+    #[test]
+    fn test_diff_encoded_list() {
+        let alice = AccountId::new_unchecked("alice.testnet".to_string());
+        testing_env!(get_context(alice.clone()).build());
+
+        let mut contract = Contract::new(alice.to_string(), None);
+        let id = contract.register_list("example/rl/1".to_string(), Some(16), None, None);
+        contract.update(id.clone(), vec![1, 3214, 5000], vec![]);
+        let cached = contract.get_encoded_list(id.clone());
+        assert_eq!(
+            contract.diff_encoded_list(id.clone(), cached.clone()),
+            ListDiff::default()
+        );
+
+        contract.update(id.clone(), vec![2, 131071], vec![3214]);
+        assert_eq!(
+            contract.diff_encoded_list(id.clone(), cached),
+            ListDiff {
+                revoked: vec![2, 131071],
+                reset: vec![3214]
+            }
+        );
+        // an empty list of the same size gives all the revoked credentials
+        let empty = RL2020::new(alice.to_string(), 16).unwrap().encoded_list();
+        assert_eq!(
+            contract.diff_encoded_list(id, empty),
+            ListDiff {
+                revoked: vec![1, 2, 5000, 131071],
+                reset: vec![]
+            }
+        );
+    }
+
+    #[test]
+    fn test_owner_and_config() {
+        let alice = AccountId::new_unchecked("alice.testnet".to_string());
+        let bob = AccountId::new_unchecked("bob.testnet".to_string());
+        testing_env!(get_context(alice.clone()).build());
+
+        let mut contract = Contract::new(alice.to_string(), None);
+        assert_eq!(contract.get_owner(), alice.to_string());
+        assert_eq!(contract.get_config(), Config::default());
+
+        let config = Config {
+            default_list_size: 8,
+            max_list_size: 32,
+            registration_fee: U128(10),
+        };
+        contract.set_config(config.clone());
+        assert_eq!(contract.get_config(), config);
+
+        contract.set_owner(bob.to_string());
+        assert_eq!(contract.get_owner(), bob.to_string());
+    }
+
+    #[test]
+    fn test_register_list_with_config() {
+        let alice = AccountId::new_unchecked("alice.testnet".to_string());
+        let mut context = get_context(alice.clone());
+        testing_env!(context.build());
+
+        let config = Config {
+            default_list_size: 8,
+            max_list_size: 16,
+            registration_fee: U128(10),
+        };
+        let mut contract = Contract::new(alice.to_string(), Some(config));
+
+        testing_env!(context.attached_deposit(10).build());
+        let id = contract.register_list("example/rl/1".to_string(), None, None, None);
+        // the last index of an 8kb list
+        assert!(!contract.is_revoked(id, 8 * 1024 * 8 - 1));
+    }
+
+    /// a contract with a registration fee of 10 and a list registered by bob, who attached 12
+    fn setup_fees() -> Contract {
+        let alice = AccountId::new_unchecked("alice.testnet".to_string());
+        let bob = AccountId::new_unchecked("bob.testnet".to_string());
+        testing_env!(get_context(alice.clone()).build());
+        let config = Config {
+            registration_fee: U128(10),
+            ..Config::default()
+        };
+        let mut contract = Contract::new(alice.to_string(), Some(config));
+        testing_env!(get_context(bob).attached_deposit(12).build());
+        contract.register_list("example/rl/1".to_string(), None, None, None);
+        testing_env!(get_context(alice).build());
+        contract
+    }
+
+    #[test]
+    fn test_withdraw_fees() {
+        let mut contract = setup_fees();
+        // the whole attached deposit is collected
+        assert_eq!(contract.get_fees_collected(), U128(12));
+
+        contract.withdraw_fees(Some(U128(5)));
+        assert_eq!(contract.get_fees_collected(), U128(7));
+        contract.withdraw_fees(None);
+        assert_eq!(contract.get_fees_collected(), U128(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_INSUFFICIENT_FEES: requested 13, available 12")]
+    fn test_withdraw_fees_above_collected() {
+        let mut contract = setup_fees();
+        contract.withdraw_fees(Some(U128(13)));
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_NOT_AUTHORIZED")]
+    fn test_withdraw_fees_not_owner() {
+        let mut contract = setup_fees();
+        let bob = AccountId::new_unchecked("bob.testnet".to_string());
+        testing_env!(get_context(bob).build());
+        contract.withdraw_fees(None);
+    }
+
+    #[test]
+    fn test_pause() {
+        let alice = AccountId::new_unchecked("alice.testnet".to_string());
+        let bob = AccountId::new_unchecked("bob.testnet".to_string());
+        testing_env!(get_context(alice.clone()).build());
+
+        let mut contract = Contract::new(alice.to_string(), None);
+        contract.set_paused(true);
+        assert!(contract.is_paused());
+        contract.set_paused(false);
+        assert!(!contract.is_paused());
+
+        // the list owner can pause its own list
+        testing_env!(get_context(bob).build());
+        let id = contract.register_list("example/rl/1".to_string(), None, None, None);
+        contract.set_list_paused(id.to_string(), true);
+        assert!(contract.is_list_paused(id.to_string()));
+        // views keep working
+        assert!(!contract.is_revoked(id.to_string(), 1));
+
+        // the contract owner can resume any list
+        testing_env!(get_context(alice).build());
+        contract.set_list_paused(id.to_string(), false);
+        assert!(!contract.is_list_paused(id.to_string()));
+    }
+
+    /// a list owned by bob, who is not the contract owner, optionally paused by its owner
+    fn setup_bob_list(paused_by_bob: bool) -> (Contract, String) {
+        let alice = AccountId::new_unchecked("alice.testnet".to_string());
+        let bob = AccountId::new_unchecked("bob.testnet".to_string());
+        testing_env!(get_context(bob).build());
+        let mut contract = Contract::new(alice.to_string(), None);
+        let id = contract.register_list("example/rl/1".to_string(), None, None, None);
+        if paused_by_bob {
+            contract.set_list_paused(id.clone(), true);
+        }
+        (contract, id)
+    }
+
+    /// bob's list paused by the contract owner, with bob as the caller
+    fn setup_owner_paused_bob_list() -> (Contract, String) {
+        let (mut contract, id) = setup_bob_list(false);
+        let alice = AccountId::new_unchecked("alice.testnet".to_string());
+        let bob = AccountId::new_unchecked("bob.testnet".to_string());
+        testing_env!(get_context(alice).build());
+        contract.set_list_paused(id.clone(), true);
+        testing_env!(get_context(bob).build());
+        (contract, id)
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_NOT_AUTHORIZED")]
+    fn test_list_owner_cannot_pause_again_owner_pause() {
+        let (mut contract, id) = setup_owner_paused_bob_list();
+        // the pause of the contract owner cannot be taken over by the list owner
+        contract.set_list_paused(id, true);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_NOT_AUTHORIZED")]
+    fn test_list_owner_cannot_resume_owner_pause() {
+        let (mut contract, id) = setup_owner_paused_bob_list();
+        contract.set_list_paused(id, false);
+    }
+
+    #[test]
+    fn test_list_owner_pause() {
+        let (mut contract, id) = setup_bob_list(true);
+        assert!(contract.is_list_paused(id.to_string()));
+        assert!(!contract.is_revoked(id.to_string(), 1));
+
+        // the list owner resumes its own list
+        contract.set_list_paused(id.to_string(), false);
+        assert!(!contract.is_list_paused(id.to_string()));
+        contract.revoke(id.to_string(), 1);
+        assert!(contract.is_revoked(id, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_PAUSED")]
+    fn test_list_owner_pause_revoke() {
+        let (mut contract, id) = setup_bob_list(true);
+        contract.revoke(id, 1);
+    }
+
+    /// a signed list owned by the contract owner, with the contract or the list paused
+    fn setup_paused(contract_paused: bool) -> (Contract, String) {
+        let (mut contract, id) = setup_signed_list();
+        match contract_paused {
+            true => contract.set_paused(true),
+            false => contract.set_list_paused(id.clone(), true),
+        }
+        (contract, id)
+    }
+
+    fn revoke_signed_paused(contract_paused: bool) {
+        let (mut contract, id) = setup_paused(contract_paused);
+        let (_, signature) = signed_update(&id, vec![3214], 1, 2_000);
+        contract.revoke_signed(
+            id,
+            vec![3214],
+            vec![],
+            U64(1),
+            U64(2_000),
+            signer_public_key(),
+            signature,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_PAUSED")]
+    fn test_paused_contract_revoke() {
+        let (mut contract, id) = setup_paused(true);
+        contract.revoke(id, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_PAUSED")]
+    fn test_paused_contract_set_list() {
+        let (mut contract, id) = setup_paused(true);
+        contract.set_list(id, Some("00".repeat(16 * 1024)), None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_PAUSED")]
+    fn test_paused_contract_register_list() {
+        let (mut contract, _) = setup_paused(true);
+        contract.register_list("example/rl/2".to_string(), None, None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_PAUSED")]
+    fn test_paused_contract_update() {
+        let (mut contract, id) = setup_paused(true);
+        contract.update(id, vec![1], vec![]);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_PAUSED")]
+    fn test_paused_contract_revoke_signed() {
+        revoke_signed_paused(true);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_PAUSED")]
+    fn test_paused_list_revoke() {
+        let (mut contract, id) = setup_paused(false);
+        contract.revoke(id, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_PAUSED")]
+    fn test_paused_list_set_list() {
+        let (mut contract, id) = setup_paused(false);
+        contract.set_list(id, Some("00".repeat(16 * 1024)), None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_PAUSED")]
+    fn test_paused_list_update() {
+        let (mut contract, id) = setup_paused(false);
+        contract.update(id, vec![1], vec![]);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_PAUSED")]
+    fn test_paused_list_revoke_signed() {
+        revoke_signed_paused(false);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_PAUSED")]
+    fn test_paused_list_set_list_signer() {
+        let (mut contract, id) = setup_paused(false);
+        contract.set_list_signer(id, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "ERR_PAUSED")]
+    fn test_paused_contract_update_metadata() {
+        let (mut contract, id) = setup_paused(true);
+        contract.update_metadata(id, ListMetadata::default());
+    }
+
+    #[test]
+    fn test_revoke_signed() {
+        let (mut contract, id) = setup_signed_list();
+        assert_eq!(
+            contract.get_list_signer(id.to_string()),
+            Some(signer_public_key())
+        );
+        assert_eq!(contract.get_list_nonce(id.to_string()), U64(0));
+
+        // bob relays the update signed by the list signer
+        let bob = AccountId::new_unchecked("bob.testnet".to_string());
+        testing_env!(get_context(bob).block_timestamp(1_000).build());
+        let (_, signature) = signed_update(&id, vec![3214], 5, 2_000);
+        contract.revoke_signed(
+            id.to_string(),
+            vec![3214],
+            vec![],
+            U64(5),
+            U64(2_000),
+            signer_public_key(),
+            signature,
+        );
+        assert!(contract.is_revoked(id.to_string(), 3214));
+        assert_eq!(contract.get_list_nonce(id.to_string()), U64(5));
+        assert_eq!(
+            contract.get_encoded_list(id.to_string()),
+            "eJztwAENAAAIwKBHs38qa7gJxSkTAAAAAAAAAAAAAAAAAAAADy3coQBB"
+        );
+    }
+
+    #[test]
+    fn test_revoke_signed_errors() {
+        let (mut contract, id) = setup_signed_list();
+        let bob = AccountId::new_unchecked("bob.testnet".to_string());
+        testing_env!(get_context(bob).block_timestamp(1_000).build());
+        let other_key = PublicKey::try_from([&[0u8][..], &[1; 32]].concat()).unwrap();
+
+        let (message, signature) = signed_update(&id, vec![1], 1, 2_000);
+        contract
+            .apply_signed_update(message, &signer_public_key(), &signature)
+            .unwrap();
+
+        // the message is replayed
+        let (message, signature) = signed_update(&id, vec![1], 1, 2_000);
+        let err = contract.apply_signed_update(message, &signer_public_key(), &signature);
+        assert_eq!(err, Err(RLError::InvalidNonce { last: 1, got: 1 }));
+        // the message is altered after signing
+        let (mut message, signature) = signed_update(&id, vec![1], 2, 2_000);
+        message.to_revoke = vec![2];
+        let err = contract.apply_signed_update(message, &signer_public_key(), &signature);
+        assert_eq!(err, Err(RLError::InvalidSignature));
+        // the message has expired
+        let (message, signature) = signed_update(&id, vec![1], 2, 999);
+        let err = contract.apply_signed_update(message, &signer_public_key(), &signature);
+        assert_eq!(err, Err(RLError::Expired));
+        // the key is not the list signer
+        let (message, signature) = signed_update(&id, vec![1], 2, 2_000);
+        let err = contract.apply_signed_update(message, &other_key, &signature);
+        assert_eq!(err, Err(RLError::Unauthorized));
+        // the list has no signer
+        let (message, signature) = signed_update("alice.testnet:example/rl/2", vec![1], 2, 2_000);
+        let err = contract.apply_signed_update(message, &signer_public_key(), &signature);
+        assert_eq!(err, Err(RLError::NotFound));
+
+        assert_eq!(contract.get_list_nonce(id.to_string()), U64(1));
+        assert!(!contract.is_revoked(id.to_string(), 2));
+    }
+
+    #[test]
+    fn test_list_issuer() {
+        let alice = AccountId::new_unchecked("alice.testnet".to_string());
+        testing_env!(get_context(alice.clone()).build());
+        let mut contract = Contract::new(alice.to_string(), None);
+
+        let issuer = Issuer {
+            did: "did:near:alice.testnet".to_string(),
+            verification_method: "did:near:alice.testnet#key-1".to_string(),
+        };
+        let id_1 =
+            contract.register_list("example/rl/1".to_string(), None, Some(issuer.clone()), None);
+        let id_2 = contract.register_list("example/rl/2".to_string(), None, None, None);
+        assert_eq!(contract.get_list_issuer(id_1), Some(issuer));
+        assert_eq!(contract.get_list_issuer(id_2), None);
+
+        let key = "did:key:z6MktwupdmLXVVqTzCw4i46r4uGyosGXRnR3XjN4Zq7oMMsw";
+        let table = [
+            (
+                "did:near:alice.testnet",
+                "did:near:alice.testnet#key-1",
+                true,
+            ),
+            (key, &format!("{}#{}", key, &key[8..]), true),
+            (
+                "did:web:example.com%3A8443",
+                "did:web:example.com%3A8443#owner",
+                true,
+            ),
+            ("alice.testnet", "alice.testnet#key-1", false),
+            ("did:near", "did:near#key-1", false),
+            ("did::alice.testnet", "did::alice.testnet#key-1", false),
+            (
+                "did:NEAR:alice.testnet",
+                "did:NEAR:alice.testnet#key-1",
+                false,
+            ),
+            (
+                "did:near:alice.testnet#key-1",
+                "did:near:alice.testnet#key-1",
+                false,
+            ),
+            (
+                "did:near:alice testnet",
+                "did:near:alice testnet#key-1",
+                false,
+            ),
+            ("did:near:alice.testnet", "did:near:alice.testnet", false),
+            ("did:near:alice.testnet", "did:near:alice.testnet#", false),
+            (
+                "did:near:alice.testnet",
+                "did:near:bob.testnet#key-1",
+                false,
+            ),
+        ];
+        for (did, verification_method, valid) in table {
+            let issuer = Issuer {
+                did: did.to_string(),
+                verification_method: verification_method.to_string(),
+            };
+            let result = issuer.validate();
+            assert_eq!(result.is_ok(), valid, "{} {}", did, verification_method);
+            if let Err(e) = result {
+                assert_eq!(e.name(), "ERR_INVALID_ISSUER");
+            }
+        }
+    }
+
+    #[test]
+    fn test_list_info() {
+        let alice = AccountId::new_unchecked("alice.testnet".to_string());
+        testing_env!(get_context(alice.clone()).block_timestamp(100).build());
+        let mut contract = Contract::new(alice.to_string(), None);
+
+        let metadata = ListMetadata {
+            description: Some("employee badges".to_string()),
+            url: Some("https://example.com/status/1".to_string()),
+            status_purpose: StatusPurpose::Suspension,
+            encoding: ListEncoding::Base64Zlib,
+        };
+        let id = contract.register_list(
+            "example/rl/1".to_string(),
+            Some(8),
+            None,
+            Some(metadata.clone()),
+        );
+        let info = contract.get_list_info(id.to_string());
+        assert_eq!(
+            info,
+            ListInfo {
+                id: id.to_string(),
+                creator: alice.to_string(),
+                size: 8,
+                description: metadata.description,
+                url: metadata.url,
+                status_purpose: StatusPurpose::Suspension,
+                encoding: ListEncoding::Base64Zlib,
+                issuer: None,
+                created_at: U64(100),
+                updated_at: U64(100),
+            }
+        );
+
+        // the list changes move the updated-at timestamp
+        testing_env!(get_context(alice.clone()).block_timestamp(200).build());
+        contract.revoke(id.to_string(), 1);
+        let info = contract.get_list_info(id.to_string());
+        assert_eq!((info.created_at, info.updated_at), (U64(100), U64(200)));
+
+        contract.update_metadata(id.to_string(), ListMetadata::default());
+        let info = contract.get_list_info(id.to_string());
+        assert_eq!(info.description, None);
+        assert_eq!(info.status_purpose, StatusPurpose::Revocation);
+
+        // the encoding of get_encoded_list follows the metadata
+        let zlib_encoded = contract.get_encoded_list(id.to_string());
+        assert!(zlib_encoded.starts_with("eJ"));
+        let metadata = ListMetadata {
+            encoding: ListEncoding::Base64Gzip,
+            ..ListMetadata::default()
+        };
+        contract.update_metadata(id.to_string(), metadata);
+        let info = contract.get_list_info(id.to_string());
+        assert_eq!(info.encoding, ListEncoding::Base64Gzip);
+        let gzip_encoded = contract.get_encoded_list(id.to_string());
+        assert!(gzip_encoded.starts_with("H4sI"));
+        assert_eq!(
+            contract.diff_encoded_list(id.to_string(), zlib_encoded),
+            ListDiff::default()
+        );
+        assert!(contract.is_revoked(id.to_string(), 1));
+
+        let table = [
+            (Some("a".repeat(256)), None, true),
+            (Some("a".repeat(257)), None, false),
+            (None, Some("http://example.com/status/1"), true),
+            (None, Some("ftp://example.com/status/1"), false),
+            (None, Some("https://"), false),
+            (None, Some("https://example.com/status 1"), false),
+        ];
+        for (description, url, valid) in table {
+            let metadata = ListMetadata {
+                description,
+                url: url.map(str::to_string),
+                status_purpose: StatusPurpose::Revocation,
+                encoding: ListEncoding::Base64Zlib,
+            };
+            assert_eq!(metadata.validate().is_ok(), valid, "{:?}", metadata);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let alice = AccountId::new_unchecked("alice.testnet".to_string());
+        testing_env!(get_context(alice).build());
+
+        let mut rl = RL2020::new("alice.testnet".to_string(), 1).unwrap();
+        // the last indexable element
+        rl.set_many(vec![8 * 1024 - 1], vec![]).unwrap();
+        assert!(rl.get(8 * 1024 - 1).unwrap());
+
+        let err = rl.get(8 * 1024).unwrap_err();
+        assert_eq!(
+            err,
+            RLError::OutOfBounds {
+                index: 8 * 1024,
+                capacity: 8 * 1024
+            }
+        );
+        assert_eq!(err.code(), 1);
+        assert_eq!(
+            err.to_string(),
+            "ERR_OUT_OF_BOUNDS: max indexable element is 8191, provided index 8192 is out of range"
+        );
+
+        let err = rl.replace(vec![0; 10]).unwrap_err();
+        assert_eq!(
+            err,
+            RLError::InvalidSize {
+                expected: 1024,
+                got: 10
+            }
+        );
+        assert_eq!(RLError::NotFound.to_string(), "ERR_RL_NOT_FOUND");
+    }
 }
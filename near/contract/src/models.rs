@@ -1,155 +1,232 @@
-use std::io::{Read, Write};
-
-use base64::{encode_config, STANDARD};
-use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::{U128, U64};
 use near_sdk::serde::{Deserialize, Serialize};
 #[allow(unused_imports)]
 use near_sdk::{env, near_bindgen};
 
 use crate::utils::AccountId;
 
-const DEFAULT_BIT_STRING_SIZE_KB: usize = 4;
-const DEFAULT_BIT_STRING_SIZE: usize = DEFAULT_BIT_STRING_SIZE_KB * 1024 * 8;
+pub use rl2020_core::DEFAULT_BIT_STRING_SIZE_KB;
+pub const MAX_BIT_STRING_SIZE_KB: usize = 16;
+pub const MAX_DESCRIPTION_LENGTH: usize = 256;
+pub const MAX_URL_LENGTH: usize = 2048;
+
+/// the revocation lists errors, shared with the Casper contract
+pub use rl2020_core::RLError;
 
-#[derive(Debug)]
-pub struct RLError {
-    pub message: String,
+/// abort the contract call with the error as the panic message
+pub trait PanicOnError {
+    fn panic(&self) -> !;
 }
 
-/// Implement the `std::error::Error` trait for `RLError`.
-impl RLError {
-    pub fn new(msg: &str) -> Self {
-        RLError {
-            message: String::from(msg),
-        }
+impl PanicOnError for RLError {
+    fn panic(&self) -> ! {
+        // like `near_sdk::require!`, outside of wasm the error is raised as a Rust panic, the
+        // mocked `panic_utf8` of the unit tests cannot unwind and would abort the test binary
+        #[cfg(not(target_arch = "wasm32"))]
+        panic!("{}", self);
+        #[cfg(target_arch = "wasm32")]
+        env::panic_str(&self.to_string())
     }
 }
 
-/// this comes from https://github.com/noandrea/rl2020.rs
-#[derive(Clone, Default, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+/// contract-wide settings, managed by the contract owner
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
 #[serde(crate = "near_sdk::serde")]
-pub struct RL2020 {
-    // the encoded list as a byte vec (compressed)
-    pub bit_set: Vec<u8>,
-    pub creator: AccountId,
+pub struct Config {
+    // size in kilobytes of a list registered without an explicit size
+    pub default_list_size: usize,
+    // maximum size in kilobytes that a list can be registered with
+    pub max_list_size: usize,
+    // deposit (in yoctoNEAR) required to register a new list
+    pub registration_fee: U128,
 }
 
-/// implement the `std::fmt::Display` trait for `RL2020`.
-impl ToString for RL2020 {
-    fn to_string(&self) -> String {
-        encode_config(&self.bit_set, STANDARD)
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            default_list_size: DEFAULT_BIT_STRING_SIZE_KB,
+            max_list_size: MAX_BIT_STRING_SIZE_KB,
+            registration_fee: U128(0),
+        }
     }
 }
 
-/// Reference implementation here
-/// https://github.com/noandrea/rl2020.rs/blob/ab747623429438334484df308884bd9da4c06e93/src/lib.rs#L105
-impl RL2020 {
-    /// constructor
-    pub fn new() -> Result<Self, RLError> {
-        // initialize the bitset
-        Ok(RL2020 {
-            bit_set: Self::pack(vec![0; DEFAULT_BIT_STRING_SIZE_KB * 1024].as_ref())?,
-            creator: env::predecessor_account_id().to_string(),
-        })
-    }
-
-    /// capacity returns the capacity of the bitset in number of elements
-    pub fn capacity(&self) -> usize {
-        DEFAULT_BIT_STRING_SIZE
-    }
-
-    /// size returns the size of the bitset in kilobytes
-    pub fn size(&self) -> usize {
-        DEFAULT_BIT_STRING_SIZE_KB
-    }
-
-    /// sets the bit at the given index to the given value
-    /// if the index is out of bounds, returns an error
-    /// if do_set is true, sets the bit to 1
-    /// if do_set is false, sets the bit to 0
-    fn set(bit_set: &mut Vec<u8>, do_set: bool, index: u64) -> Result<(), RLError> {
-        // check bounds
-        Self::check_bounds(bit_set.len(), index)?;
-        // calculate the position of the bit
-        let pos = (index / 8) as usize;
-        let j = (index % 8) as u8;
-        // set the bit
-        match do_set {
-            true => bit_set[pos] |= 1 << j,
-            false => bit_set[pos] &= !(1 << j),
-        };
-        Ok(())
-    }
-
-    /// sets the bits at the given indexes to the given values
-    pub fn set_many(&mut self, to_set: Vec<u64>, to_unset: Vec<u64>) -> Result<(), RLError> {
-        let mut bit_set = Self::unpack(&self.bit_set)?;
-        for i in to_set {
-            Self::set(&mut bit_set, true, i)?;
+impl Config {
+    /// validate checks that the configured sizes are consistent
+    pub fn validate(&self) -> Result<(), RLError> {
+        if self.default_list_size == 0 || self.max_list_size == 0 {
+            return Err(RLError::InvalidConfig("list size must be greater than 0"));
         }
-        for i in to_unset {
-            Self::set(&mut bit_set, false, i)?;
+        if self.default_list_size > self.max_list_size {
+            return Err(RLError::InvalidConfig(
+                "default list size cannot exceed the max list size",
+            ));
         }
-        self.bit_set = Self::pack(&bit_set)?;
         Ok(())
     }
+}
 
-    /// replaces the bitset with the given one
-    pub fn replace(&mut self, new_bit_set: Vec<u8>) -> Result<(), RLError> {
-        if new_bit_set.len() != DEFAULT_BIT_STRING_SIZE_KB * 1024 {
-            return Err(RLError::new("invalid bitset size"));
+/// the identity of the issuer publishing a list, used as the `issuer` of the list credentials
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Issuer {
+    // the issuer DID, e.g. did:near:<account> or did:key:<key>
+    pub did: String,
+    // the DID URL of the key signing the list credentials, e.g. <did>#key-1
+    pub verification_method: String,
+}
+
+impl Issuer {
+    /// validate checks that the DID has a method and an identifier and that
+    /// the verification method belongs to the DID
+    pub fn validate(&self) -> Result<(), RLError> {
+        let mut parts = self.did.splitn(3, ':');
+        let valid_did = parts.next() == Some("did")
+            && parts.next().is_some_and(|method| {
+                !method.is_empty()
+                    && method
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+            })
+            && parts.next().is_some_and(|id| !id.is_empty())
+            && !self.did.contains(['#', '?', '/'])
+            && !self.did.contains(char::is_whitespace);
+        if !valid_did {
+            return Err(RLError::InvalidIssuer("the issuer is not a valid DID"));
+        }
+        let fragment = self
+            .verification_method
+            .strip_prefix(&self.did)
+            .and_then(|vm| vm.strip_prefix('#'));
+        if !fragment.is_some_and(|f| !f.is_empty() && !f.contains(char::is_whitespace)) {
+            return Err(RLError::InvalidIssuer(
+                "the verification method must be a fragment of the issuer DID",
+            ));
         }
-        self.bit_set = Self::pack(&new_bit_set)?;
         Ok(())
     }
+}
 
-    /// returns the value of the bit at the given index
-    /// if the index is out of bounds, returns an error
-    /// if the bit is 0, returns false
-    /// if the bit is 1, returns true
-    pub fn get(&self, index: u64) -> Result<bool, RLError> {
-        Self::check_bounds(self.capacity(), index)?;
+/// the purpose of the credential statuses in a list
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, BorshDeserialize, BorshSerialize,
+)]
+#[serde(crate = "near_sdk::serde", rename_all = "lowercase")]
+pub enum StatusPurpose {
+    #[default]
+    Revocation,
+    Suspension,
+}
 
-        let pos = (index / 8) as usize;
-        let j = (index % 8) as u8;
+/// the encoding of the list returned by `get_encoded_list`, the bitset is base64 encoded
+/// after a zlib or a GZIP (as required by the list credentials) compression
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, BorshDeserialize, BorshSerialize,
+)]
+#[serde(crate = "near_sdk::serde")]
+pub enum ListEncoding {
+    #[default]
+    #[serde(rename = "base64-zlib")]
+    Base64Zlib,
+    #[serde(rename = "base64-gzip")]
+    Base64Gzip,
+}
 
-        let bit_set = Self::unpack(&self.bit_set)?;
+/// the descriptive settings of a list, managed by the list owner
+#[derive(
+    Clone, Debug, Default, PartialEq, Serialize, Deserialize, BorshDeserialize, BorshSerialize,
+)]
+#[serde(crate = "near_sdk::serde", default)]
+pub struct ListMetadata {
+    // a human readable description of the list
+    pub description: Option<String>,
+    // the public URL the list credential is served from
+    pub url: Option<String>,
+    pub status_purpose: StatusPurpose,
+    pub encoding: ListEncoding,
+}
 
-        match bit_set[pos] & (1 << j) {
-            0 => Ok(false),
-            _ => Ok(true),
+impl ListMetadata {
+    /// validate checks the length of the description and that the URL is an http(s) URL
+    pub fn validate(&self) -> Result<(), RLError> {
+        if self
+            .description
+            .as_ref()
+            .is_some_and(|d| d.len() > MAX_DESCRIPTION_LENGTH)
+        {
+            return Err(RLError::InvalidMetadata("the description is too long"));
+        }
+        if let Some(url) = &self.url {
+            if url.len() > MAX_URL_LENGTH {
+                return Err(RLError::InvalidMetadata("the url is too long"));
+            }
+            let host = url
+                .strip_prefix("https://")
+                .or_else(|| url.strip_prefix("http://"));
+            if !host.is_some_and(|h| !h.is_empty() && !h.contains(char::is_whitespace)) {
+                return Err(RLError::InvalidMetadata("the url must be an http(s) URL"));
+            }
         }
+        Ok(())
     }
+}
 
-    /// pack encodes and compresses the bitset
-    fn pack(data: &Vec<u8>) -> Result<Vec<u8>, RLError> {
-        // compress the data
-        let mut e = ZlibEncoder::new(Vec::new(), Compression::default());
-        e.write_all(data)
-            .map_err(|e| RLError::new(&e.to_string()))?;
-        let compressed = e.finish().map_err(|e| RLError::new(&e.to_string()))?;
-        // encode the data
-        Ok(compressed)
-    }
+/// the block timestamps (in nanoseconds) of the registration and of the last change of a list
+#[derive(Clone, Copy, Debug, PartialEq, BorshDeserialize, BorshSerialize)]
+pub struct Timestamps {
+    pub created_at: u64,
+    pub updated_at: u64,
+}
 
-    /// unpack decodes and decompresses the bitset
-    fn unpack(data: &Vec<u8>) -> Result<Vec<u8>, RLError> {
-        let mut d = ZlibDecoder::new(&data[..]);
-        let mut buf = Vec::new();
-        d.read_to_end(&mut buf)
-            .map_err(|e| RLError::new(&e.to_string()))?;
-        Ok(buf)
-    }
+/// the description of a list returned by the `get_list_info` view
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ListInfo {
+    pub id: String,
+    pub creator: AccountId,
+    // the size of the list in kilobytes
+    pub size: usize,
+    pub description: Option<String>,
+    pub url: Option<String>,
+    pub status_purpose: StatusPurpose,
+    pub encoding: ListEncoding,
+    pub issuer: Option<Issuer>,
+    pub created_at: U64,
+    pub updated_at: U64,
+}
+
+/// the indexes changed since a previous version of a list, returned by `diff_encoded_list`
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ListDiff {
+    pub revoked: Vec<u64>,
+    pub reset: Vec<u64>,
+}
 
-    /// check_bounds checks if the index is within the bounds of the bitset
-    fn check_bounds(size: usize, index: u64) -> Result<(), RLError> {
-        if (index as usize) >= size {
-            return Err(RLError::new(&format!(
-                "max indexable element is {}, provided index {} is out of range",
-                size, index,
-            )));
+impl From<rl2020_core::ListDiff> for ListDiff {
+    fn from(diff: rl2020_core::ListDiff) -> Self {
+        ListDiff {
+            revoked: diff.revoked,
+            reset: diff.reset,
         }
-        Ok(())
     }
 }
+
+/// the message signed by the signer of a list to authorize a `revoke_signed` update,
+/// the signature is over its Borsh serialization
+#[derive(BorshSerialize)]
+pub struct SignedUpdate {
+    // the contract account, so that the message cannot be replayed on another deployment
+    pub contract: AccountId,
+    pub id: String,
+    pub to_revoke: Vec<u64>,
+    pub to_reset: Vec<u64>,
+    // must be greater than the last nonce used for the list
+    pub nonce: u64,
+    // block timestamp in nanoseconds after which the message is rejected
+    pub expiry: u64,
+}
+
+/// this comes from https://github.com/noandrea/rl2020.rs
+pub type RL2020 = rl2020_core::RL2020<AccountId>;
@@ -1,7 +1,40 @@
-use near_sdk::{env, PromiseResult};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use near_sdk::{env, CurveType, PromiseResult, PublicKey};
+use rl2020_core::{Chain, RLError};
 
 pub type AccountId = String;
 
+/// the NEAR implementation of the chain specific concerns of the lists
+pub struct Near;
+
+impl Chain for Near {
+    type AccountId = AccountId;
+
+    fn caller() -> AccountId {
+        env::predecessor_account_id().to_string()
+    }
+}
+
+/// verify_ed25519 checks the signature of a message against an Ed25519 public key
+pub fn verify_ed25519(
+    public_key: &PublicKey,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<(), RLError> {
+    if public_key.curve_type() != CurveType::ED25519 {
+        return Err(RLError::InvalidSignature);
+    }
+    // the first byte of the key is the curve type
+    let key = public_key.as_bytes()[1..]
+        .try_into()
+        .ok()
+        .and_then(|key| VerifyingKey::from_bytes(key).ok())
+        .ok_or(RLError::InvalidSignature)?;
+    let signature = Signature::from_slice(signature).map_err(|_| RLError::InvalidSignature)?;
+    key.verify(message, &signature)
+        .map_err(|_| RLError::InvalidSignature)
+}
+
 pub fn assert_self() {
     let caller = env::predecessor_account_id();
     let current = env::current_account_id();
@@ -17,9 +50,7 @@ pub fn assert_single_promise_success() {
     );
 
     match env::promise_result(0) {
-        PromiseResult::Successful(_) => return,
+        PromiseResult::Successful(_) => (),
         _ => panic!("Expected PromiseStatus to be successful"),
     };
 }
-
-
@@ -0,0 +1,76 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use base64::{decode_config, STANDARD};
+use casper_types::bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH};
+use casper_types::{ApiError, CLType, CLTyped};
+
+use crate::{RLError, RL2020};
+
+/// The `RLError` codes are shifted by this offset so that they don't overlap
+/// with the errors defined by the contract.
+pub const RL_ERROR_CODE_OFFSET: u16 = 100;
+
+impl From<RLError> for ApiError {
+    fn from(error: RLError) -> ApiError {
+        ApiError::User(RL_ERROR_CODE_OFFSET + error.code())
+    }
+}
+
+/// the list is stored as a `(String, A, u32)` tuple holding the encoded list,
+/// the creator and the size, so that clients reading the dictionary item
+/// can decode it without knowing the contract types
+impl<A: CLTyped> CLTyped for RL2020<A> {
+    fn cl_type() -> CLType {
+        CLType::Tuple3([
+            Box::new(String::cl_type()),
+            Box::new(A::cl_type()),
+            Box::new(u32::cl_type()),
+        ])
+    }
+}
+
+/// the bitset is base64 encoded once per serialization, the length of the encoded list
+/// is computed without encoding it
+impl<A: ToBytes> ToBytes for RL2020<A> {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.extend(self.encoded_list().to_bytes()?);
+        buffer.extend(self.creator.to_bytes()?);
+        buffer.extend((self.size as u32).to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        // a string is serialized as its u32 length followed by its bytes,
+        // the padded base64 encoding takes 4 bytes for every 3 bytes started
+        let len = self.bit_set.len();
+        let encoded_len = match len % 3 {
+            0 => len / 3 * 4,
+            _ => len / 3 * 4 + 4,
+        };
+        U32_SERIALIZED_LENGTH
+            + encoded_len
+            + self.creator.serialized_length()
+            + U32_SERIALIZED_LENGTH
+    }
+}
+
+impl<A: FromBytes> FromBytes for RL2020<A> {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (encoded_list, remainder) = String::from_bytes(bytes)?;
+        let (creator, remainder) = A::from_bytes(remainder)?;
+        let (size, remainder) = u32::from_bytes(remainder)?;
+        let bit_set =
+            decode_config(encoded_list, STANDARD).map_err(|_| bytesrepr::Error::Formatting)?;
+        Ok((
+            RL2020 {
+                bit_set,
+                creator,
+                size: size as usize,
+            },
+            remainder,
+        ))
+    }
+}
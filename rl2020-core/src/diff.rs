@@ -0,0 +1,43 @@
+use alloc::vec::Vec;
+
+use crate::{RLError, RL2020};
+
+/// the indexes that changed between two versions of a list
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ListDiff {
+    /// the indexes revoked in the current version and not in the previous one, in order
+    pub revoked: Vec<u64>,
+    /// the indexes revoked in the previous version and reset in the current one, in order
+    pub reset: Vec<u64>,
+}
+
+impl ListDiff {
+    /// between compares two (uncompressed) bitsets of the same size
+    pub(crate) fn between(previous: &[u8], current: &[u8]) -> Self {
+        let mut diff = ListDiff::default();
+        for (pos, (old, new)) in previous.iter().zip(current).enumerate() {
+            let mut changed = old ^ new;
+            while changed != 0 {
+                let j = changed.trailing_zeros();
+                let index = pos as u64 * 8 + j as u64;
+                match new & (1 << j) {
+                    0 => diff.reset.push(index),
+                    _ => diff.revoked.push(index),
+                }
+                changed &= changed - 1;
+            }
+        }
+        diff
+    }
+
+    /// is_empty returns true if the two versions are the same
+    pub fn is_empty(&self) -> bool {
+        self.revoked.is_empty() && self.reset.is_empty()
+    }
+}
+
+/// diff_encoded_lists returns the indexes revoked and reset from the previous to the current
+/// encoded list (compressed and base64 encoded), the two lists must have the same size
+pub fn diff_encoded_lists(previous: &str, current: &str) -> Result<ListDiff, RLError> {
+    RL2020::from_encoded_list(current, ())?.diff(previous)
+}
@@ -0,0 +1,173 @@
+use alloc::string::String;
+use core::fmt;
+
+/// errors returned by the revocation lists contract
+///
+/// every variant has a stable numeric code and a stable name,
+/// the name is used as the prefix of the NEAR panic message
+/// and the code is used for the Casper `ApiError::User` value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RLError {
+    /// the index is outside of the list capacity
+    OutOfBounds { index: u64, capacity: usize },
+    /// the stored bitset could not be decompressed
+    Decompression(DecompressionError),
+    /// the bitset or the requested list size is not valid
+    InvalidSize { expected: usize, got: usize },
+    /// the input could not be decoded
+    InvalidEncoding(String),
+    /// the contract settings are not consistent
+    InvalidConfig(&'static str),
+    /// the list id is not valid
+    InvalidListId,
+    /// the requested list size exceeds the configured maximum
+    ListTooLarge { size: usize, max: usize },
+    /// the caller is not allowed to perform the operation
+    Unauthorized,
+    /// the list does not exist
+    NotFound,
+    /// a list with the same id already exists
+    AlreadyExists,
+    /// the contract or the list is paused
+    Paused,
+    /// the attached deposit does not cover the registration fee
+    InsufficientDeposit { required: u128, attached: u128 },
+    /// the signature of a signed message does not match the list signer
+    InvalidSignature,
+    /// the signed message has expired
+    Expired,
+    /// the nonce of a signed message has already been used
+    InvalidNonce { last: u64, got: u64 },
+    /// the issuer DID or verification method of a list is not valid
+    InvalidIssuer(&'static str),
+    /// the description or the URL of a list is not valid
+    InvalidMetadata(&'static str),
+    /// the new bitset resets an index that is revoked in the current one
+    RevokedBitCleared { index: u64 },
+    /// the requested withdrawal exceeds the collected registration fees
+    InsufficientFees { requested: u128, available: u128 },
+}
+
+impl RLError {
+    /// code returns the stable numeric code of the error,
+    /// the code 2 of the removed compression error is not reused
+    pub fn code(&self) -> u16 {
+        match self {
+            RLError::OutOfBounds { .. } => 1,
+            RLError::Decompression(_) => 3,
+            RLError::InvalidSize { .. } => 4,
+            RLError::InvalidEncoding(_) => 5,
+            RLError::InvalidConfig(_) => 6,
+            RLError::InvalidListId => 7,
+            RLError::ListTooLarge { .. } => 8,
+            RLError::Unauthorized => 9,
+            RLError::NotFound => 10,
+            RLError::AlreadyExists => 11,
+            RLError::Paused => 12,
+            RLError::InsufficientDeposit { .. } => 13,
+            RLError::InvalidSignature => 14,
+            RLError::Expired => 15,
+            RLError::InvalidNonce { .. } => 16,
+            RLError::InvalidIssuer(_) => 17,
+            RLError::InvalidMetadata(_) => 18,
+            RLError::RevokedBitCleared { .. } => 19,
+            RLError::InsufficientFees { .. } => 20,
+        }
+    }
+
+    /// name returns the stable name of the error
+    pub fn name(&self) -> &'static str {
+        match self {
+            RLError::OutOfBounds { .. } => "ERR_OUT_OF_BOUNDS",
+            RLError::Decompression(_) => "ERR_DECOMPRESSION",
+            RLError::InvalidSize { .. } => "ERR_INVALID_SIZE",
+            RLError::InvalidEncoding(_) => "ERR_INVALID_ENCODING",
+            RLError::InvalidConfig(_) => "ERR_INVALID_CONFIG",
+            RLError::InvalidListId => "ERR_INVALID_RL_LIST",
+            RLError::ListTooLarge { .. } => "ERR_RL_SIZE_TOO_LARGE",
+            RLError::Unauthorized => "ERR_NOT_AUTHORIZED",
+            RLError::NotFound => "ERR_RL_NOT_FOUND",
+            RLError::AlreadyExists => "ERR_RL_EXISTS",
+            RLError::Paused => "ERR_PAUSED",
+            RLError::InsufficientDeposit { .. } => "ERR_INSUFFICIENT_DEPOSIT",
+            RLError::InvalidSignature => "ERR_INVALID_SIGNATURE",
+            RLError::Expired => "ERR_EXPIRED",
+            RLError::InvalidNonce { .. } => "ERR_INVALID_NONCE",
+            RLError::InvalidIssuer(_) => "ERR_INVALID_ISSUER",
+            RLError::InvalidMetadata(_) => "ERR_INVALID_METADATA",
+            RLError::RevokedBitCleared { .. } => "ERR_REVOKED_BIT_CLEARED",
+            RLError::InsufficientFees { .. } => "ERR_INSUFFICIENT_FEES",
+        }
+    }
+}
+
+/// the reasons a compressed bitset cannot be decompressed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecompressionError {
+    /// the zlib or GZIP stream ends before its end marker
+    Truncated,
+    /// the data is not a valid zlib or GZIP stream
+    Malformed,
+    /// the checksum of the zlib or GZIP stream does not match the inflated data
+    ChecksumMismatch,
+    /// the bitset inflates to more than the given number of bytes
+    TooLarge { max: usize },
+}
+
+impl fmt::Display for DecompressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecompressionError::Truncated => write!(f, "the compressed bitset is truncated"),
+            DecompressionError::Malformed => write!(f, "the compressed bitset is malformed"),
+            DecompressionError::ChecksumMismatch => {
+                write!(f, "the checksum of the compressed bitset does not match")
+            }
+            DecompressionError::TooLarge { max } => {
+                write!(f, "the bitset inflates to more than {} bytes", max)
+            }
+        }
+    }
+}
+
+/// the error is displayed as `<name>` or `<name>: <details>`
+impl fmt::Display for RLError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())?;
+        match self {
+            RLError::OutOfBounds { index, capacity } => write!(
+                f,
+                ": max indexable element is {}, provided index {} is out of range",
+                capacity.saturating_sub(1),
+                index
+            ),
+            RLError::InvalidEncoding(e) => write!(f, ": {}", e),
+            RLError::Decompression(e) => write!(f, ": {}", e),
+            RLError::InvalidSize { expected, got } => {
+                write!(f, ": expected {}, got {}", expected, got)
+            }
+            RLError::InvalidConfig(e) | RLError::InvalidIssuer(e) | RLError::InvalidMetadata(e) => {
+                write!(f, ": {}", e)
+            }
+            RLError::ListTooLarge { size, max } => {
+                write!(f, ": max list size is {}kb, got {}kb", max, size)
+            }
+            RLError::InsufficientDeposit { required, attached } => {
+                write!(f, ": required {}, attached {}", required, attached)
+            }
+            RLError::InvalidNonce { last, got } => {
+                write!(f, ": nonce must be greater than {}, got {}", last, got)
+            }
+            RLError::RevokedBitCleared { index } => {
+                write!(f, ": index {} is revoked and cannot be reset", index)
+            }
+            RLError::InsufficientFees {
+                requested,
+                available,
+            } => write!(f, ": requested {}, available {}", requested, available),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RLError {}
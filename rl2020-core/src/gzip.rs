@@ -0,0 +1,115 @@
+//! GZIP ([RFC 1952](https://www.rfc-editor.org/rfc/rfc1952)) framing of the deflate streams,
+//! the compression required for the `encodedList` of the list credentials.
+//!
+//! Only single member streams are supported, the optional header fields are skipped.
+use alloc::vec::Vec;
+
+use miniz_oxide::deflate::compress_to_vec;
+use miniz_oxide::inflate::{decompress_to_vec_with_limit, TINFLStatus};
+
+use crate::DecompressionError;
+
+const MAGIC: [u8; 2] = [0x1f, 0x8b];
+const METHOD_DEFLATE: u8 = 8;
+const HEADER_LENGTH: usize = 10;
+const TRAILER_LENGTH: usize = 8;
+// the operating system field of the header, unknown
+const OS_UNKNOWN: u8 = 255;
+
+const FLAG_HCRC: u8 = 0x02;
+const FLAG_EXTRA: u8 = 0x04;
+const FLAG_NAME: u8 = 0x08;
+const FLAG_COMMENT: u8 = 0x10;
+
+/// is_gzip returns true if the data starts with the GZIP magic bytes
+pub(crate) fn is_gzip(data: &[u8]) -> bool {
+    data.starts_with(&MAGIC)
+}
+
+/// compress wraps the deflate stream of the data in a GZIP member with no modification time
+pub(crate) fn compress(data: &[u8], level: u8) -> Vec<u8> {
+    let mut out = Vec::with_capacity(HEADER_LENGTH + TRAILER_LENGTH + data.len() / 64);
+    out.extend_from_slice(&MAGIC);
+    out.extend_from_slice(&[METHOD_DEFLATE, 0, 0, 0, 0, 0, 0, OS_UNKNOWN]);
+    out.extend_from_slice(&compress_to_vec(data, level));
+    out.extend_from_slice(&crc32(data).to_le_bytes());
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out
+}
+
+/// decompress inflates a GZIP member, the decompression is aborted as soon as the output
+/// exceeds max bytes
+pub(crate) fn decompress(data: &[u8], max: usize) -> Result<Vec<u8>, DecompressionError> {
+    if data.len() < HEADER_LENGTH + TRAILER_LENGTH {
+        return Err(DecompressionError::Truncated);
+    }
+    if !is_gzip(data) || data[2] != METHOD_DEFLATE {
+        return Err(DecompressionError::Malformed);
+    }
+    let flags = data[3];
+    let mut pos = HEADER_LENGTH;
+    if flags & FLAG_EXTRA != 0 {
+        let len = data
+            .get(pos..pos + 2)
+            .ok_or(DecompressionError::Truncated)?;
+        pos += 2 + u16::from_le_bytes([len[0], len[1]]) as usize;
+    }
+    for flag in [FLAG_NAME, FLAG_COMMENT] {
+        if flags & flag != 0 {
+            let end = data
+                .get(pos..)
+                .and_then(|rest| rest.iter().position(|b| *b == 0))
+                .ok_or(DecompressionError::Truncated)?;
+            pos += end + 1;
+        }
+    }
+    if flags & FLAG_HCRC != 0 {
+        pos += 2;
+    }
+    if pos > data.len() - TRAILER_LENGTH {
+        return Err(DecompressionError::Truncated);
+    }
+
+    let (body, trailer) = data[pos..].split_at(data.len() - pos - TRAILER_LENGTH);
+    let out = decompress_to_vec_with_limit(body, max).map_err(|e| match e {
+        TINFLStatus::HasMoreOutput => DecompressionError::TooLarge { max },
+        TINFLStatus::NeedsMoreInput | TINFLStatus::FailedCannotMakeProgress => {
+            DecompressionError::Truncated
+        }
+        _ => DecompressionError::Malformed,
+    })?;
+    let crc = u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+    let len = u32::from_le_bytes([trailer[4], trailer[5], trailer[6], trailer[7]]);
+    if crc != crc32(&out) || len != out.len() as u32 {
+        return Err(DecompressionError::ChecksumMismatch);
+    }
+    Ok(out)
+}
+
+/// the CRC-32 lookup table of the reversed polynomial 0xEDB88320
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut c = i as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 {
+                0xEDB8_8320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[i] = c;
+        i += 1;
+    }
+    table
+};
+
+/// crc32 computes the CRC-32 checksum of the GZIP trailer
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, b| {
+        CRC_TABLE[((crc ^ *b as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
@@ -0,0 +1,39 @@
+use crate::RLError;
+
+/// the maximum length of a list id in bytes
+pub const MAX_LIST_ID_LENGTH: usize = 128;
+
+/// the characters that separate the segments of a list id
+const SEPARATORS: [char; 5] = ['-', '_', '.', '/', ':'];
+
+/// validate_list_id checks that a list id is in its canonical form:
+///
+/// - it is not empty and is at most [`MAX_LIST_ID_LENGTH`] bytes long
+/// - it contains only ASCII letters, digits and the `-_./:` separators, so that no whitespace,
+///   control characters or Unicode look-alikes (composed or decomposed forms, homoglyphs) are
+///   accepted and every valid id is already Unicode normalized
+/// - it starts and ends with a letter or a digit and has no consecutive separators
+///
+/// the ids are not rewritten, an id that is not canonical is rejected
+pub fn validate_list_id(id: &str) -> Result<(), RLError> {
+    if id.is_empty() || id.len() > MAX_LIST_ID_LENGTH {
+        return Err(RLError::InvalidListId);
+    }
+    if !id
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || SEPARATORS.contains(&c))
+    {
+        return Err(RLError::InvalidListId);
+    }
+    let is_separator = |c: Option<char>| matches!(c, Some(c) if SEPARATORS.contains(&c));
+    if is_separator(id.chars().next()) || is_separator(id.chars().last()) {
+        return Err(RLError::InvalidListId);
+    }
+    let mut chars = id.chars().peekable();
+    while let Some(c) = chars.next() {
+        if SEPARATORS.contains(&c) && is_separator(chars.peek().copied()) {
+            return Err(RLError::InvalidListId);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,534 @@
+//! Platform agnostic implementation of the [RevocationList2020](https://w3c-ccg.github.io/vc-status-rl-2020/)
+//! bitset shared by the NEAR and Casper revocation lists contracts.
+//!
+//! The chain specific concerns (the caller identity and the storage of the lists)
+//! are provided by the contracts through the [`Chain`] and [`ListStore`] traits.
+#![no_std]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "casper")]
+mod casper;
+mod diff;
+mod error;
+mod gzip;
+mod id;
+mod list;
+mod store;
+
+#[cfg(feature = "casper")]
+pub use casper::RL_ERROR_CODE_OFFSET;
+pub use diff::{diff_encoded_lists, ListDiff};
+pub use error::{DecompressionError, RLError};
+pub use id::{validate_list_id, MAX_LIST_ID_LENGTH};
+pub use list::RL2020;
+pub use store::{Chain, ListStore};
+
+/// the default size of a list in kilobytes
+pub const DEFAULT_BIT_STRING_SIZE_KB: usize = 4;
+
+/// the max size in kilobytes of a list decoded from its encoded form without a known size,
+/// 8 million credentials, the decompression of larger lists is aborted
+pub const MAX_DECODED_LIST_SIZE_KB: usize = 1024;
+
+/// the encoding of the encoded lists, the bitset is zlib compressed and base64 encoded
+pub const LIST_ENCODING: &str = "base64-zlib";
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use alloc::collections::BTreeMap;
+    use alloc::string::{String, ToString};
+    use alloc::vec;
+    use core::cell::RefCell;
+
+    // the encoded lists for a 16kb list, as served by the contracts
+    const EMPTY_LIST: &str = "eJztwDEBAAAAwqD1T20MHygAAAAAAAAAAAAAAAAAAADgbUAAAAE=";
+    const LIST_WITH_3214_REVOKED: &str = "eJztwAENAAAIwKBHs38qa7gJxSkTAAAAAAAAAAAAAAAAAAAADy3coQBB";
+
+    std::thread_local! {
+        static CALLER: RefCell<String> = const { RefCell::new(String::new()) };
+    }
+
+    struct TestChain;
+
+    impl Chain for TestChain {
+        type AccountId = String;
+
+        fn caller() -> String {
+            CALLER.with(|c| c.borrow().clone())
+        }
+    }
+
+    fn set_caller(caller: &str) {
+        CALLER.with(|c| *c.borrow_mut() = caller.to_string());
+    }
+
+    #[derive(Default)]
+    struct TestStore(BTreeMap<String, RL2020<String>>);
+
+    impl ListStore<TestChain> for TestStore {
+        fn get_list(&self, id: &str) -> Option<RL2020<String>> {
+            self.0.get(id).cloned()
+        }
+
+        fn put_list(&mut self, id: &str, rl: &RL2020<String>) {
+            self.0.insert(id.to_string(), rl.clone());
+        }
+    }
+
+    #[test]
+    fn test_encoding() {
+        let mut rl = RL2020::new((), 16).unwrap();
+        assert_eq!(rl.encoded_list(), EMPTY_LIST);
+
+        rl.revoke(3214).unwrap();
+        assert!(rl.is_revoked(3214).unwrap());
+        assert_eq!(rl.encoded_list(), LIST_WITH_3214_REVOKED);
+
+        rl.reset(3214).unwrap();
+        assert!(!rl.is_revoked(3214).unwrap());
+        assert_eq!(rl.encoded_list(), EMPTY_LIST);
+
+        // resets take precedence over revocations
+        rl.set_many(vec![1, 2], vec![2]).unwrap();
+        assert!(rl.get(1).unwrap());
+        assert!(!rl.get(2).unwrap());
+    }
+
+    #[test]
+    fn test_decoding() {
+        let rl = RL2020::from_encoded_list(LIST_WITH_3214_REVOKED, ()).unwrap();
+        assert_eq!(rl.size(), 16);
+        assert!(rl.is_revoked(3214).unwrap());
+        assert!(!rl.is_revoked(3213).unwrap());
+        assert_eq!(rl.encoded_list(), LIST_WITH_3214_REVOKED);
+
+        assert!(matches!(
+            RL2020::from_encoded_list("not base64!", ()),
+            Err(RLError::InvalidEncoding(_))
+        ));
+        // valid base64 but not a zlib stream
+        assert!(matches!(
+            RL2020::from_encoded_list("aGVsbG8gd29ybGQ=", ()),
+            Err(RLError::Decompression(DecompressionError::Malformed))
+        ));
+        // the bitset must be a whole number of kilobytes
+        let mut rl = RL2020::new((), 1).unwrap();
+        rl.bit_set = miniz_oxide::deflate::compress_to_vec_zlib(&[0; 1000], 6);
+        assert_eq!(
+            RL2020::from_encoded_list(&rl.encoded_list(), ()),
+            Err(RLError::InvalidSize {
+                expected: 1024,
+                got: 1000
+            })
+        );
+    }
+
+    #[test]
+    fn test_gzip() {
+        // a 16kb bitset with the index 3214 set as defined by the specs, the left-most bit of
+        // the byte 401, GZIP compressed with Python's gzip module
+        const GZIP_LIST_WITH_3214_REVOKED: &str =
+            "H4sIAAAAAAACA+3QAQkAAAwDoLH+oVfjcI1gwilVAAAAAAAAAAAAAAAAAAAA8NEAv0BjMgBAAAA=";
+        // the empty list of the StatusList2021 spec example, with the URL safe alphabet
+        const SPEC_EMPTY_LIST: &str =
+            "H4sIAAAAAAAAA-3BMQEAAADCoPVPbQwfoAAAAAAAAAAAAAAAAAAAAIC3AYbSVKsAQAAA";
+
+        let rl = RL2020::from_encoded_list(GZIP_LIST_WITH_3214_REVOKED, ()).unwrap();
+        assert_eq!(rl.size(), 16);
+        assert!(rl.is_revoked(3214).unwrap());
+        assert!(!rl.is_revoked(3209).unwrap());
+        assert!(!rl.is_revoked(3213).unwrap());
+        assert_eq!(
+            rl.decode_bit_set(GZIP_LIST_WITH_3214_REVOKED).unwrap(),
+            rl.decode_bit_set(LIST_WITH_3214_REVOKED).unwrap()
+        );
+        let rl = RL2020::from_encoded_list(SPEC_EMPTY_LIST, ()).unwrap();
+        assert_eq!(rl.size(), 16);
+        assert_eq!(
+            rl.decode_bit_set(EMPTY_LIST),
+            rl.decode_bit_set(SPEC_EMPTY_LIST)
+        );
+
+        let rl = RL2020::from_encoded_list(LIST_WITH_3214_REVOKED, ()).unwrap();
+        let encoded = rl.gzip_encoded_list().unwrap();
+        assert!(encoded.starts_with("H4sI"));
+        // the index 3214 is the bit 0x02 of the byte 401 in the order of the specs
+        let mut bit_set = gzip::decompress(&base64::decode(&encoded).unwrap(), 16 * 1024).unwrap();
+        assert_eq!(bit_set[401], 0x02);
+        bit_set[401] = 0;
+        assert!(bit_set.iter().all(|b| *b == 0));
+        assert_eq!(
+            rl.decode_bit_set(&encoded).unwrap(),
+            rl.decode_bit_set(GZIP_LIST_WITH_3214_REVOKED).unwrap()
+        );
+
+        // the trailer holds the checksum and the length of the bitset
+        let mut data = base64::decode(&encoded).unwrap();
+        let len = data.len();
+        data[len - 8] ^= 1;
+        let corrupted = base64::encode(&data);
+        assert_eq!(
+            RL2020::from_encoded_list(&corrupted, ()),
+            Err(RLError::Decompression(DecompressionError::ChecksumMismatch))
+        );
+        let truncated = base64::encode(&data[..len - 9]);
+        assert!(matches!(
+            RL2020::from_encoded_list(&truncated, ()),
+            Err(RLError::Decompression(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_bit_set() {
+        let rl = RL2020::new((), 16).unwrap();
+        let bit_set = rl.decode_bit_set(LIST_WITH_3214_REVOKED).unwrap();
+        assert_eq!(bit_set.len(), 16 * 1024);
+        assert_eq!(bit_set[3214 / 8], 1 << (3214 % 8));
+
+        // the bitset must have the size of the list
+        let rl = RL2020::new((), 1).unwrap();
+        assert_eq!(
+            rl.decode_bit_set(LIST_WITH_3214_REVOKED),
+            Err(RLError::Decompression(DecompressionError::TooLarge {
+                max: 1024
+            }))
+        );
+        let small = RL2020::new((), 1).unwrap().encoded_list();
+        assert_eq!(
+            RL2020::new((), 2).unwrap().decode_bit_set(&small),
+            Err(RLError::InvalidSize {
+                expected: 2048,
+                got: 1024
+            })
+        );
+        // a few hundred bytes that inflate to 8mb are rejected without being inflated
+        let bomb = RL2020::<()> {
+            bit_set: miniz_oxide::deflate::compress_to_vec_zlib(&vec![0; 8 << 20], 10),
+            creator: (),
+            size: 1,
+        };
+        assert!(bomb.bit_set.len() < 10_000);
+        assert_eq!(
+            rl.decode_bit_set(&bomb.encoded_list()),
+            Err(RLError::Decompression(DecompressionError::TooLarge {
+                max: 1024
+            }))
+        );
+        assert!(matches!(
+            rl.decode_bit_set("not base64!"),
+            Err(RLError::InvalidEncoding(_))
+        ));
+    }
+
+    // a deterministic generator for the fuzz tests, so that the failures can be reproduced
+    fn xorshift(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    #[test]
+    fn test_corrupted_bit_set() {
+        let mut rl = RL2020::new((), 16).unwrap();
+        rl.set_many(vec![0, 3214, 16 * 8192 - 1], vec![]).unwrap();
+        let valid = rl.bit_set.clone();
+
+        // every truncation of a valid stream is rejected, never read past the input
+        for len in 0..valid.len() {
+            let mut truncated = rl.clone();
+            truncated.bit_set.truncate(len);
+            assert!(
+                matches!(
+                    truncated.get(3214),
+                    Err(RLError::Decompression(
+                        DecompressionError::Truncated | DecompressionError::Malformed
+                    ))
+                ),
+                "truncated at {}",
+                len
+            );
+        }
+
+        // random corruptions either fail with a typed error or decode to a bitset of the
+        // size of the list, the accessors never panic
+        let mut state = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..2000 {
+            let mut corrupted = rl.clone();
+            for _ in 0..=xorshift(&mut state) % 4 {
+                let pos = xorshift(&mut state) as usize % valid.len();
+                corrupted.bit_set[pos] ^= 1 << (xorshift(&mut state) % 8);
+            }
+            let index = xorshift(&mut state) % corrupted.capacity() as u64;
+            match corrupted.get(index) {
+                Ok(_) => assert!(corrupted.decode_bit_set(&corrupted.encoded_list()).is_ok()),
+                Err(RLError::Decompression(_)) | Err(RLError::InvalidSize { .. }) => {}
+                Err(e) => panic!("unexpected error {:?}", e),
+            }
+            let _ = corrupted.set_many(vec![index], vec![]);
+            let _ = RL2020::from_encoded_list(&corrupted.encoded_list(), ());
+        }
+        // random bytes
+        for len in 0..200 {
+            let data: alloc::vec::Vec<u8> = (0..len).map(|_| xorshift(&mut state) as u8).collect();
+            let garbage = RL2020::<()> {
+                bit_set: data,
+                creator: (),
+                size: 1,
+            };
+            assert!(
+                garbage.get(0).is_err() || garbage.decode_bit_set(&garbage.encoded_list()).is_ok()
+            );
+        }
+
+        // a stored bitset shorter or longer than the list size is an error, not a panic
+        let short = RL2020::<()> {
+            bit_set: miniz_oxide::deflate::compress_to_vec_zlib(&[0xff; 1024], 6),
+            creator: (),
+            size: 16,
+        };
+        assert_eq!(
+            short.get(16 * 8192 - 1),
+            Err(RLError::InvalidSize {
+                expected: 16 * 1024,
+                got: 1024
+            })
+        );
+        let long = RL2020::<()> {
+            bit_set: miniz_oxide::deflate::compress_to_vec_zlib(&[0; 1025], 6),
+            creator: (),
+            size: 1,
+        };
+        assert_eq!(
+            long.get(0),
+            Err(RLError::Decompression(DecompressionError::TooLarge {
+                max: 1024
+            }))
+        );
+        let mut checksum = valid.clone();
+        let last = checksum.len() - 1;
+        checksum[last] ^= 1;
+        rl.bit_set = checksum;
+        assert_eq!(
+            rl.get(0),
+            Err(RLError::Decompression(DecompressionError::ChecksumMismatch))
+        );
+
+        // lists decoded without a known size are bounded too
+        let huge = RL2020::<()> {
+            bit_set: miniz_oxide::deflate::compress_to_vec_zlib(
+                &vec![0; (MAX_DECODED_LIST_SIZE_KB + 1) * 1024],
+                10,
+            ),
+            creator: (),
+            size: MAX_DECODED_LIST_SIZE_KB + 1,
+        };
+        assert_eq!(
+            RL2020::from_encoded_list(&huge.encoded_list(), ()),
+            Err(RLError::Decompression(DecompressionError::TooLarge {
+                max: MAX_DECODED_LIST_SIZE_KB * 1024
+            }))
+        );
+    }
+
+    #[test]
+    fn test_diff() {
+        let mut rl = RL2020::new((), 16).unwrap();
+        assert!(rl.diff(EMPTY_LIST).unwrap().is_empty());
+        assert_eq!(
+            rl.diff(LIST_WITH_3214_REVOKED).unwrap(),
+            ListDiff {
+                revoked: vec![],
+                reset: vec![3214]
+            }
+        );
+
+        rl.set_many(vec![0, 7, 8, 3214, 16 * 8192 - 1], vec![])
+            .unwrap();
+        let previous = rl.encoded_list();
+        rl.set_many(vec![1, 9, 16 * 8192 - 2], vec![0, 3214])
+            .unwrap();
+        assert_eq!(
+            rl.diff(&previous).unwrap(),
+            ListDiff {
+                revoked: vec![1, 9, 16 * 8192 - 2],
+                reset: vec![0, 3214]
+            }
+        );
+        assert_eq!(
+            diff_encoded_lists(&previous, &rl.encoded_list()),
+            rl.diff(&previous)
+        );
+        assert_eq!(
+            diff_encoded_lists(&rl.encoded_list(), &previous).unwrap(),
+            ListDiff {
+                revoked: vec![0, 3214],
+                reset: vec![1, 9, 16 * 8192 - 2]
+            }
+        );
+
+        // the lists must have the same size
+        let small = RL2020::new((), 1).unwrap().encoded_list();
+        assert_eq!(
+            rl.diff(&small),
+            Err(RLError::InvalidSize {
+                expected: 16 * 1024,
+                got: 1024
+            })
+        );
+        assert_eq!(
+            diff_encoded_lists(&previous, &small),
+            Err(RLError::Decompression(DecompressionError::TooLarge {
+                max: 1024
+            }))
+        );
+    }
+
+    #[cfg(feature = "casper")]
+    #[test]
+    fn test_casper_bytes() {
+        use casper_types::bytesrepr::{FromBytes, ToBytes};
+
+        for (size, revoked) in [
+            (1, vec![]),
+            (1, vec![7]),
+            (16, vec![3214]),
+            (64, vec![1, 2]),
+        ] {
+            let mut rl = RL2020::new(String::from("alice"), size).unwrap();
+            rl.set_many(revoked, vec![]).unwrap();
+            let bytes = rl.to_bytes().unwrap();
+            assert_eq!(bytes.len(), rl.serialized_length());
+            assert_eq!(RL2020::<String>::from_bytes(&bytes).unwrap(), (rl, &[][..]));
+        }
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut rl = RL2020::new((), 1).unwrap();
+        assert_eq!(rl.capacity(), 8192);
+        rl.revoke(8191).unwrap();
+        assert_eq!(
+            rl.revoke(8192),
+            Err(RLError::OutOfBounds {
+                index: 8192,
+                capacity: 8192
+            })
+        );
+        assert_eq!(
+            rl.get(8192),
+            Err(RLError::OutOfBounds {
+                index: 8192,
+                capacity: 8192
+            })
+        );
+        assert_eq!(
+            RL2020::new((), 0),
+            Err(RLError::InvalidSize {
+                expected: 1,
+                got: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_list_id() {
+        let max = "a".repeat(MAX_LIST_ID_LENGTH);
+        let too_long = "a".repeat(MAX_LIST_ID_LENGTH + 1);
+        let table = [
+            // valid ids
+            ("rl/1", true),
+            ("a", true),
+            ("alice.testnet:acme/employees-2023_v1", true),
+            ("Example/RL/1", true),
+            (max.as_str(), true),
+            // length
+            ("", false),
+            (too_long.as_str(), false),
+            // whitespace and control characters
+            (" ", false),
+            (" rl/1", false),
+            ("rl/1 ", false),
+            ("rl/1\t", false),
+            ("rl/1\n", false),
+            ("rl\u{0}1", false),
+            ("rl 1", false),
+            ("rl\u{a0}1", false),
+            // unicode, composed and decomposed forms and look-alikes
+            ("rl/\u{e9}", false),
+            ("rl/e\u{301}", false),
+            ("rl/\u{430}", false),
+            ("rl\u{2215}1", false),
+            ("rl\u{ff0f}1", false),
+            // characters outside of the charset
+            ("rl#1", false),
+            ("rl?1", false),
+            ("rl\\1", false),
+            ("rl%201", false),
+            // separators
+            ("/rl/1", false),
+            ("rl/1/", false),
+            (":rl", false),
+            ("rl.", false),
+            ("rl//1", false),
+            ("rl/./1", false),
+            ("rl/../1", false),
+            ("rl:/1", false),
+            ("rl-_1", false),
+        ];
+        for (id, valid) in table {
+            let expected = if valid {
+                Ok(())
+            } else {
+                Err(RLError::InvalidListId)
+            };
+            assert_eq!(validate_list_id(id), expected, "{:?}", id);
+        }
+    }
+
+    #[test]
+    fn test_store() {
+        let mut store = TestStore::default();
+        set_caller("alice");
+
+        assert_eq!(store.register_list(" ", 1), Err(RLError::InvalidListId));
+        store.register_list("rl/1", 1).unwrap();
+        assert_eq!(store.register_list("rl/1", 1), Err(RLError::AlreadyExists));
+        assert_eq!(store.load_list("rl/1").unwrap().creator, "alice");
+        assert_eq!(store.load_list("rl/2"), Err(RLError::NotFound));
+
+        store.update_list("rl/1", vec![10], vec![]).unwrap();
+        assert!(store.load_list("rl/1").unwrap().get(10).unwrap());
+
+        // only the owner can update a list
+        set_caller("bob");
+        assert_eq!(
+            store.update_list("rl/1", vec![], vec![10]),
+            Err(RLError::Unauthorized)
+        );
+        assert_eq!(
+            store.replace_list("rl/1", vec![0; 1024], false),
+            Err(RLError::Unauthorized)
+        );
+
+        // the revoked indexes can be protected from a replacement
+        set_caller("alice");
+        assert_eq!(
+            store.replace_list("rl/1", vec![0; 1024], true),
+            Err(RLError::RevokedBitCleared { index: 10 })
+        );
+        let mut bit_set = vec![0; 1024];
+        bit_set[1] = 0b0000_0100;
+        bit_set[2] = 1;
+        store.replace_list("rl/1", bit_set, true).unwrap();
+        assert!(store.load_list("rl/1").unwrap().get(16).unwrap());
+        store.replace_list("rl/1", vec![0; 1024], false).unwrap();
+        assert!(!store.load_list("rl/1").unwrap().get(10).unwrap());
+    }
+}
@@ -0,0 +1,274 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+
+use base64::{decode_config, encode_config, STANDARD, URL_SAFE};
+#[cfg(feature = "borsh")]
+use borsh::{BorshDeserialize, BorshSerialize};
+use miniz_oxide::deflate::compress_to_vec_zlib;
+use miniz_oxide::inflate::{decompress_to_vec_zlib_with_limit, TINFLStatus};
+
+use crate::{gzip, DecompressionError, ListDiff, RLError, MAX_DECODED_LIST_SIZE_KB};
+
+// the flate2 default compression level
+const COMPRESSION_LEVEL: u8 = 6;
+
+/// this comes from https://github.com/noandrea/rl2020.rs
+///
+/// `A` is the account type of the chain the list is stored on
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "borsh", derive(BorshDeserialize, BorshSerialize))]
+pub struct RL2020<A> {
+    // the encoded list as a byte vec (compressed)
+    pub bit_set: Vec<u8>,
+    pub creator: A,
+    // the size of the bitset in kilobytes
+    pub size: usize,
+}
+
+/// the list is displayed in its encoded form (compressed and base64 encoded)
+impl<A> fmt::Display for RL2020<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", encode_config(&self.bit_set, STANDARD))
+    }
+}
+
+/// Reference implementation here
+/// https://github.com/noandrea/rl2020.rs/blob/ab747623429438334484df308884bd9da4c06e93/src/lib.rs#L105
+impl<A> RL2020<A> {
+    /// constructor, size is the size of the bitset in kilobytes
+    pub fn new(creator: A, size: usize) -> Result<Self, RLError> {
+        if size == 0 {
+            return Err(RLError::InvalidSize {
+                expected: 1,
+                got: size,
+            });
+        }
+        // initialize the bitset
+        Ok(RL2020 {
+            bit_set: Self::pack(&vec![0; size * 1024]),
+            creator,
+            size,
+        })
+    }
+
+    /// builds a list from its encoded form (zlib or GZIP compressed and base64 encoded),
+    /// the size is derived from the length of the decompressed bitset,
+    /// up to [`MAX_DECODED_LIST_SIZE_KB`]
+    pub fn from_encoded_list(encoded_list: &str, creator: A) -> Result<Self, RLError> {
+        let bit_set = decode_base64(encoded_list)?;
+        let len = Self::inflate(&bit_set, MAX_DECODED_LIST_SIZE_KB * 1024)?.len();
+        if len == 0 || len % 1024 != 0 {
+            return Err(RLError::InvalidSize {
+                expected: (len / 1024 + 1) * 1024,
+                got: len,
+            });
+        }
+        Ok(RL2020 {
+            bit_set,
+            creator,
+            size: len / 1024,
+        })
+    }
+
+    /// encoded_list returns the list compressed and base64 encoded
+    pub fn encoded_list(&self) -> String {
+        self.to_string()
+    }
+
+    /// gzip_encoded_list returns the list GZIP compressed and base64 encoded, with the bits
+    /// in the order of the specs, the `encodedList` of the list credentials
+    pub fn gzip_encoded_list(&self) -> Result<String, RLError> {
+        let mut bit_set = self.unpack()?;
+        reverse_bit_order(&mut bit_set);
+        Ok(encode_config(
+            gzip::compress(&bit_set, COMPRESSION_LEVEL),
+            STANDARD,
+        ))
+    }
+
+    /// capacity returns the capacity of the bitset in number of elements
+    pub fn capacity(&self) -> usize {
+        self.size * 1024 * 8
+    }
+
+    /// size returns the size of the bitset in kilobytes
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// sets the bit at the given index to the given value
+    /// if the index is out of bounds, returns an error
+    /// if do_set is true, sets the bit to 1
+    /// if do_set is false, sets the bit to 0
+    fn set(bit_set: &mut [u8], do_set: bool, index: u64) -> Result<(), RLError> {
+        // check bounds
+        Self::check_bounds(bit_set.len() * 8, index)?;
+        // calculate the position of the bit
+        let pos = (index / 8) as usize;
+        let j = (index % 8) as u8;
+        // set the bit
+        match do_set {
+            true => bit_set[pos] |= 1 << j,
+            false => bit_set[pos] &= !(1 << j),
+        };
+        Ok(())
+    }
+
+    /// sets the bits at the given indexes to the given values,
+    /// the bits to unset are applied after the bits to set
+    pub fn set_many(&mut self, to_set: Vec<u64>, to_unset: Vec<u64>) -> Result<(), RLError> {
+        let mut bit_set = self.unpack()?;
+        for i in to_set {
+            Self::set(&mut bit_set, true, i)?;
+        }
+        for i in to_unset {
+            Self::set(&mut bit_set, false, i)?;
+        }
+        self.bit_set = Self::pack(&bit_set);
+        Ok(())
+    }
+
+    /// revoke sets the bit at the given index
+    pub fn revoke(&mut self, index: u64) -> Result<(), RLError> {
+        self.set_many(vec![index], vec![])
+    }
+
+    /// reset unsets the bit at the given index
+    pub fn reset(&mut self, index: u64) -> Result<(), RLError> {
+        self.set_many(vec![], vec![index])
+    }
+
+    /// replaces the bitset with the given one
+    pub fn replace(&mut self, new_bit_set: Vec<u8>) -> Result<(), RLError> {
+        if new_bit_set.len() != self.size * 1024 {
+            return Err(RLError::InvalidSize {
+                expected: self.size * 1024,
+                got: new_bit_set.len(),
+            });
+        }
+        self.bit_set = Self::pack(&new_bit_set);
+        Ok(())
+    }
+
+    /// decode_bit_set decodes an encoded list (compressed and base64 encoded) into a bitset
+    /// of the size of the list, the decompression stops as soon as the output exceeds the
+    /// size of the list so that a small input cannot inflate to an arbitrary large bitset
+    pub fn decode_bit_set(&self, encoded_list: &str) -> Result<Vec<u8>, RLError> {
+        Self::inflate_exact(&decode_base64(encoded_list)?, self.size * 1024)
+    }
+
+    /// check_revoked_kept returns an error with the first index that is revoked in the list
+    /// and not in the given (uncompressed) bitset
+    pub fn check_revoked_kept(&self, new_bit_set: &[u8]) -> Result<(), RLError> {
+        let bit_set = self.unpack()?;
+        for (pos, (old, new)) in bit_set.iter().zip(new_bit_set).enumerate() {
+            let cleared = old & !new;
+            if cleared != 0 {
+                return Err(RLError::RevokedBitCleared {
+                    index: pos as u64 * 8 + cleared.trailing_zeros() as u64,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// diff returns the indexes revoked and reset since the previous version of the list,
+    /// given in its encoded form, that must have the size of the list
+    pub fn diff(&self, previous_encoded: &str) -> Result<ListDiff, RLError> {
+        let previous = self.decode_bit_set(previous_encoded)?;
+        Ok(ListDiff::between(&previous, &self.unpack()?))
+    }
+
+    /// returns the value of the bit at the given index
+    /// if the index is out of bounds, returns an error
+    /// if the bit is 0, returns false
+    /// if the bit is 1, returns true
+    pub fn get(&self, index: u64) -> Result<bool, RLError> {
+        Self::check_bounds(self.capacity(), index)?;
+
+        let pos = (index / 8) as usize;
+        let j = (index % 8) as u8;
+
+        let bit_set = self.unpack()?;
+
+        match bit_set[pos] & (1 << j) {
+            0 => Ok(false),
+            _ => Ok(true),
+        }
+    }
+
+    /// is_revoked is an alias of get
+    pub fn is_revoked(&self, index: u64) -> Result<bool, RLError> {
+        self.get(index)
+    }
+
+    /// pack compresses the bitset
+    fn pack(data: &[u8]) -> Vec<u8> {
+        compress_to_vec_zlib(data, COMPRESSION_LEVEL)
+    }
+
+    /// unpack decompresses the bitset, a stored bitset that does not inflate to exactly
+    /// the size of the list is rejected
+    fn unpack(&self) -> Result<Vec<u8>, RLError> {
+        Self::inflate_exact(&self.bit_set, self.size * 1024)
+    }
+
+    /// inflate_exact decompresses a bitset that must inflate to exactly len bytes
+    fn inflate_exact(data: &[u8], len: usize) -> Result<Vec<u8>, RLError> {
+        let bit_set = Self::inflate(data, len)?;
+        if bit_set.len() != len {
+            return Err(RLError::InvalidSize {
+                expected: len,
+                got: bit_set.len(),
+            });
+        }
+        Ok(bit_set)
+    }
+
+    /// inflate decompresses a zlib stream, or a GZIP one as found in the list credentials
+    /// whose bits are reordered as the contracts store them,
+    /// the decompression is aborted as soon as the output exceeds max bytes
+    fn inflate(data: &[u8], max: usize) -> Result<Vec<u8>, RLError> {
+        if gzip::is_gzip(data) {
+            let mut bit_set = gzip::decompress(data, max).map_err(RLError::Decompression)?;
+            reverse_bit_order(&mut bit_set);
+            return Ok(bit_set);
+        }
+        decompress_to_vec_zlib_with_limit(data, max).map_err(|e| {
+            RLError::Decompression(match e {
+                TINFLStatus::HasMoreOutput => DecompressionError::TooLarge { max },
+                TINFLStatus::NeedsMoreInput | TINFLStatus::FailedCannotMakeProgress => {
+                    DecompressionError::Truncated
+                }
+                TINFLStatus::Adler32Mismatch => DecompressionError::ChecksumMismatch,
+                _ => DecompressionError::Malformed,
+            })
+        })
+    }
+
+    /// check_bounds checks if the index is within the capacity (in bits) of the bitset
+    fn check_bounds(capacity: usize, index: u64) -> Result<(), RLError> {
+        if index >= capacity as u64 {
+            return Err(RLError::OutOfBounds { index, capacity });
+        }
+        Ok(())
+    }
+}
+
+/// decode_base64 decodes an encoded list, the list credentials may use the URL safe alphabet
+fn decode_base64(encoded_list: &str) -> Result<Vec<u8>, RLError> {
+    decode_config(encoded_list, STANDARD)
+        .or_else(|e| decode_config(encoded_list, URL_SAFE).map_err(|_| e))
+        .map_err(|e| RLError::InvalidEncoding(e.to_string()))
+}
+
+/// reverse_bit_order converts between the bit order of the contracts, where the index 0 is the
+/// least significant bit of the first byte, and the one of the specs, where it is the most
+/// significant (left-most) bit
+fn reverse_bit_order(bit_set: &mut [u8]) {
+    for byte in bit_set {
+        *byte = byte.reverse_bits();
+    }
+}
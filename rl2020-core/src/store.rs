@@ -0,0 +1,79 @@
+use alloc::vec::Vec;
+
+use crate::{validate_list_id, RLError, RL2020};
+
+/// Chain abstracts the platform the contract runs on
+pub trait Chain {
+    /// the account type used to identify the list owners
+    type AccountId: Clone + PartialEq;
+
+    /// caller returns the account that is calling the contract
+    fn caller() -> Self::AccountId;
+}
+
+/// ListStore is the storage of the revocation lists of a contract,
+/// the provided methods implement the list operations shared by all the contracts
+pub trait ListStore<C: Chain> {
+    /// get_list returns the list with the given id, if any
+    fn get_list(&self, id: &str) -> Option<RL2020<C::AccountId>>;
+
+    /// put_list stores the list under the given id
+    fn put_list(&mut self, id: &str, rl: &RL2020<C::AccountId>);
+
+    /// load_list returns the list with the given id or `RLError::NotFound`
+    fn load_list(&self, id: &str) -> Result<RL2020<C::AccountId>, RLError> {
+        self.get_list(id).ok_or(RLError::NotFound)
+    }
+
+    /// load_owned_list returns the list with the given id if the caller is the list owner
+    fn load_owned_list(&self, id: &str) -> Result<RL2020<C::AccountId>, RLError> {
+        let rl = self.load_list(id)?;
+        if rl.creator != C::caller() {
+            return Err(RLError::Unauthorized);
+        }
+        Ok(rl)
+    }
+
+    /// register_list creates a new empty list of the given size (in kilobytes)
+    /// owned by the caller, the id must pass [`validate_list_id`]
+    fn register_list(&mut self, id: &str, size: usize) -> Result<(), RLError> {
+        validate_list_id(id)?;
+        if self.get_list(id).is_some() {
+            return Err(RLError::AlreadyExists);
+        }
+        let rl = RL2020::new(C::caller(), size)?;
+        self.put_list(id, &rl);
+        Ok(())
+    }
+
+    /// update_list revokes and resets the given indexes of a list owned by the caller,
+    /// resets take precedence over revocations
+    fn update_list(
+        &mut self,
+        id: &str,
+        to_revoke: Vec<u64>,
+        to_reset: Vec<u64>,
+    ) -> Result<(), RLError> {
+        let mut rl = self.load_owned_list(id)?;
+        rl.set_many(to_revoke, to_reset)?;
+        self.put_list(id, &rl);
+        Ok(())
+    }
+
+    /// replace_list replaces the (uncompressed) bitset of a list owned by the caller,
+    /// if keep_revoked is true the new bitset cannot reset the revoked indexes
+    fn replace_list(
+        &mut self,
+        id: &str,
+        bit_set: Vec<u8>,
+        keep_revoked: bool,
+    ) -> Result<(), RLError> {
+        let mut rl = self.load_owned_list(id)?;
+        if keep_revoked {
+            rl.check_revoked_kept(&bit_set)?;
+        }
+        rl.replace(bit_set)?;
+        self.put_list(id, &rl);
+        Ok(())
+    }
+}
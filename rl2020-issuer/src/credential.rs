@@ -0,0 +1,160 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use rl2020_core::RL2020;
+use rl2020_verifier::{StatusKind, StatusPurpose};
+use serde_json::{json, Value};
+
+use crate::IssueError;
+
+const CREDENTIALS_CONTEXT: &str = "https://www.w3.org/2018/credentials/v1";
+const RL2020_CONTEXT: &str = "https://w3id.org/vc-revocation-list-2020/v1";
+const SL2021_CONTEXT: &str = "https://w3id.org/vc/status-list/2021/v1";
+/// the minimum size in kilobytes of the list of a credential (131072 entries) required by the
+/// specs, the smaller lists are padded with valid entries
+pub const MIN_LIST_SIZE_KB: usize = 16;
+/// the multicodec prefix of an Ed25519 public key
+const ED25519_MULTICODEC: [u8; 2] = [0xed, 0x01];
+
+/// the fields of a list credential besides the encoded list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CredentialOptions {
+    /// the URL the list credential is served from, referenced by the credential status entries
+    pub id: String,
+    /// the DID of the issuer
+    pub issuer: String,
+    pub kind: StatusKind,
+    /// the purpose of the list, RevocationList2020 lists are revocation lists only
+    pub purpose: StatusPurpose,
+    pub issuance_date: DateTime<Utc>,
+}
+
+/// the issuer recorded for a list by the contract, as returned by the `get_list_issuer` view
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListIssuer {
+    pub did: String,
+    pub verification_method: String,
+}
+
+impl ListIssuer {
+    /// parses the `{"did": ..., "verification_method": ...}` view result
+    pub fn from_json(value: &Value) -> Result<Self, IssueError> {
+        let field = |name: &str| {
+            value
+                .get(name)
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .ok_or_else(|| IssueError::InvalidInput(format!("the list issuer has no {}", name)))
+        };
+        Ok(ListIssuer {
+            did: field("did")?,
+            verification_method: field("verification_method")?,
+        })
+    }
+
+    /// check_key verifies that the verification method is a method of the issuer DID and,
+    /// for a `did:key` issuer, that the DID and the verification method are the ones of the
+    /// signing key. The other DID methods cannot be resolved offline and the key is accepted
+    pub fn check_key(&self, key: &VerifyingKey) -> Result<(), IssueError> {
+        let fragment = self
+            .verification_method
+            .strip_prefix(&self.did)
+            .and_then(|vm| vm.strip_prefix('#'))
+            .unwrap_or_default();
+        if fragment.is_empty() {
+            return Err(IssueError::IssuerMismatch(format!(
+                "the verification method {} is not a method of {}",
+                self.verification_method, self.did
+            )));
+        }
+        if !self.did.starts_with("did:key:") {
+            return Ok(());
+        }
+        if self.did != did_key(key) {
+            return Err(IssueError::IssuerMismatch(format!(
+                "the list is issued by {}, the signing key is {}",
+                self.did,
+                did_key(key)
+            )));
+        }
+        if self.verification_method != did_key_verification_method(key) {
+            return Err(IssueError::IssuerMismatch(format!(
+                "the verification method {} is not the signing key {}",
+                self.verification_method,
+                did_key_verification_method(key)
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// list_credential wraps an encoded list, as returned by `get_encoded_list`, into an unsigned
+/// RevocationList2020Credential or StatusList2021Credential. The contracts compress the lists
+/// with zlib, the list is compressed again with GZIP and the bit order of the specs, and padded
+/// to [`MIN_LIST_SIZE_KB`]
+pub fn list_credential(
+    options: &CredentialOptions,
+    encoded_list: &str,
+) -> Result<Value, IssueError> {
+    let mut rl = RL2020::from_encoded_list(encoded_list, ())?;
+    if rl.size() < MIN_LIST_SIZE_KB {
+        let mut bit_set = rl.decode_bit_set(encoded_list)?;
+        bit_set.resize(MIN_LIST_SIZE_KB * 1024, 0);
+        rl = RL2020::new((), MIN_LIST_SIZE_KB)?;
+        rl.replace(bit_set)?;
+    }
+    let encoded_list = rl.gzip_encoded_list()?;
+
+    let mut subject = json!({
+        "id": format!("{}#list", options.id),
+        "type": options.kind.subject_type(),
+        "encodedList": encoded_list,
+    });
+    let list_context = match options.kind {
+        StatusKind::RevocationList2020 => RL2020_CONTEXT,
+        StatusKind::StatusList2021 => {
+            subject["statusPurpose"] = json!(options.purpose.to_string());
+            SL2021_CONTEXT
+        }
+    };
+    Ok(json!({
+        "@context": [CREDENTIALS_CONTEXT, list_context],
+        "id": options.id,
+        "type": ["VerifiableCredential", options.kind.credential_type()],
+        "issuer": options.issuer,
+        "issuanceDate": format_date(&options.issuance_date),
+        "credentialSubject": subject,
+    }))
+}
+
+/// read_encoded_list extracts the encoded list from the output of `get_encoded_list` or from
+/// a snapshot file, ignoring the surrounding whitespace and quotes printed by the clients
+pub fn read_encoded_list(input: &str) -> &str {
+    input.trim().trim_matches(|c| c == '"' || c == '\'').trim()
+}
+
+/// signing_key parses a hex encoded Ed25519 secret key seed
+pub fn signing_key(seed: &str) -> Result<SigningKey, IssueError> {
+    let seed = hex::decode(seed.trim()).map_err(|e| IssueError::InvalidKey(e.to_string()))?;
+    let seed: [u8; 32] = seed
+        .try_into()
+        .map_err(|_| IssueError::InvalidKey("the seed must be 32 bytes".to_string()))?;
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+/// did_key returns the `did:key` DID of an Ed25519 public key
+pub fn did_key(key: &VerifyingKey) -> String {
+    let mut bytes = ED25519_MULTICODEC.to_vec();
+    bytes.extend_from_slice(key.as_bytes());
+    format!("did:key:z{}", bs58::encode(bytes).into_string())
+}
+
+/// did_key_verification_method returns the verification method of a `did:key` DID
+pub fn did_key_verification_method(key: &VerifyingKey) -> String {
+    let did = did_key(key);
+    let fragment = did.trim_start_matches("did:key:");
+    format!("{}#{}", did, fragment)
+}
+
+pub(crate) fn format_date(date: &DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
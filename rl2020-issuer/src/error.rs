@@ -0,0 +1,59 @@
+use std::fmt;
+
+use rl2020_core::RLError;
+
+/// errors returned when issuing or verifying a signed list credential
+///
+/// every variant has a stable name, used as the prefix of the error message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IssueError {
+    /// the encoded list is not a valid RevocationList2020 bitset
+    InvalidList(RLError),
+    /// the signing key could not be parsed
+    InvalidKey(String),
+    /// the credential has no valid `DataIntegrityProof` proof
+    InvalidProof(String),
+    /// the token is not a valid VC-JWT
+    InvalidJwt(String),
+    /// the issuer or the key do not match the issuer recorded for the list
+    IssuerMismatch(String),
+    /// an input of the tool, such as the list issuer view result, is malformed
+    InvalidInput(String),
+}
+
+impl IssueError {
+    /// name returns the stable name of the error
+    pub fn name(&self) -> &'static str {
+        match self {
+            IssueError::InvalidList(_) => "ERR_INVALID_LIST",
+            IssueError::InvalidKey(_) => "ERR_INVALID_KEY",
+            IssueError::InvalidProof(_) => "ERR_INVALID_PROOF",
+            IssueError::InvalidJwt(_) => "ERR_INVALID_JWT",
+            IssueError::IssuerMismatch(_) => "ERR_ISSUER_MISMATCH",
+            IssueError::InvalidInput(_) => "ERR_INVALID_INPUT",
+        }
+    }
+}
+
+/// the error is displayed as `<name>: <details>`
+impl fmt::Display for IssueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.name())?;
+        match self {
+            IssueError::InvalidList(e) => write!(f, "{}", e),
+            IssueError::InvalidKey(reason)
+            | IssueError::InvalidProof(reason)
+            | IssueError::InvalidJwt(reason)
+            | IssueError::IssuerMismatch(reason)
+            | IssueError::InvalidInput(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl std::error::Error for IssueError {}
+
+impl From<RLError> for IssueError {
+    fn from(e: RLError) -> Self {
+        IssueError::InvalidList(e)
+    }
+}
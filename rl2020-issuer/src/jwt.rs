@@ -0,0 +1,86 @@
+//! VC-JWT encoding of the list credentials, signed with `EdDSA`.
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::DateTime;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde_json::{json, Value};
+
+use crate::IssueError;
+
+const ALGORITHM: &str = "EdDSA";
+
+/// sign_jwt encodes the credential as a VC-JWT, with the `iss`, `jti` and `nbf` claims taken
+/// from the credential `issuer`, `id` and `issuanceDate`
+pub fn sign_jwt(
+    credential: &Value,
+    key: &SigningKey,
+    verification_method: &str,
+) -> Result<String, IssueError> {
+    let issuance_date = credential
+        .get("issuanceDate")
+        .and_then(Value::as_str)
+        .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+        .ok_or_else(|| {
+            IssueError::InvalidJwt("the issuanceDate is not a valid date".to_string())
+        })?;
+
+    let header = json!({
+        "alg": ALGORITHM,
+        "typ": "JWT",
+        "kid": verification_method,
+    });
+    let claims = json!({
+        "iss": credential["issuer"],
+        "jti": credential["id"],
+        "nbf": issuance_date.timestamp(),
+        "vc": credential,
+    });
+    let signing_input = format!("{}.{}", encode(&header), encode(&claims));
+    let signature = key.sign(signing_input.as_bytes());
+    Ok(format!(
+        "{}.{}",
+        signing_input,
+        URL_SAFE_NO_PAD.encode(signature.to_bytes())
+    ))
+}
+
+/// verify_jwt checks the signature of a VC-JWT and returns the credential
+pub fn verify_jwt(token: &str, key: &VerifyingKey) -> Result<Value, IssueError> {
+    let invalid = |reason: &str| IssueError::InvalidJwt(reason.to_string());
+
+    let (signing_input, signature) = token
+        .rsplit_once('.')
+        .ok_or_else(|| invalid("the token is not a JWS"))?;
+    let (header, claims) = signing_input
+        .split_once('.')
+        .ok_or_else(|| invalid("the token is not a JWS"))?;
+    if decode(header)?.get("alg") != Some(&json!(ALGORITHM)) {
+        return Err(invalid("the token algorithm is not EdDSA"));
+    }
+    let signature = URL_SAFE_NO_PAD
+        .decode(signature)
+        .ok()
+        .and_then(|s| Signature::from_slice(&s).ok())
+        .ok_or_else(|| invalid("the token signature is malformed"))?;
+    key.verify(signing_input.as_bytes(), &signature)
+        .map_err(|_| invalid("the signature does not match"))?;
+
+    decode(claims)?
+        .get("vc")
+        .cloned()
+        .ok_or_else(|| invalid("the token has no vc claim"))
+}
+
+fn encode(value: &Value) -> String {
+    URL_SAFE_NO_PAD.encode(value.to_string())
+}
+
+fn decode(segment: &str) -> Result<Value, IssueError> {
+    URL_SAFE_NO_PAD
+        .decode(segment)
+        .ok()
+        .and_then(|s| serde_json::from_slice(&s).ok())
+        .ok_or_else(|| {
+            IssueError::InvalidJwt("the token segment is not base64url json".to_string())
+        })
+}
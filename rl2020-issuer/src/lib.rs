@@ -0,0 +1,362 @@
+//! Signed list credentials produced from the state of the revocation lists contracts.
+//!
+//! The encoded list returned by the `get_encoded_list` method of the NEAR and Casper contracts
+//! (or read from a snapshot file) is wrapped into a RevocationList2020Credential or a
+//! StatusList2021Credential, with the list GZIP compressed and at least 16KB as required by the
+//! specs, and signed with an Ed25519 key either with a `DataIntegrityProof` (`eddsa-jcs-2022`)
+//! or as a VC-JWT.
+mod credential;
+mod error;
+mod jwt;
+mod proof;
+
+pub use credential::{
+    did_key, did_key_verification_method, list_credential, read_encoded_list, signing_key,
+    CredentialOptions, ListIssuer, MIN_LIST_SIZE_KB,
+};
+pub use error::IssueError;
+pub use jwt::{sign_jwt, verify_jwt};
+pub use proof::{sign_proof, verify_proof};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use ed25519_dalek::SigningKey;
+    use rl2020_core::RLError;
+    use rl2020_verifier::{verify_status, CredentialState, StatusKind, StatusPurpose};
+    use serde_json::{json, Value};
+
+    // the encoded list for a 16kb list with the index 3214 revoked, as served by the contracts
+    const LIST_WITH_3214_REVOKED: &str = "eJztwAENAAAIwKBHs38qa7gJxSkTAAAAAAAAAAAAAAAAAAAADy3coQBB";
+    // the same list with the bit order of the specs (the bit 0x02 of the byte 401), GZIP
+    // compressed by Python's gzip module
+    const SPEC_LIST_WITH_3214_REVOKED: &str =
+        "H4sIAAAAAAACA+3QAQkAAAwDoLH+oVfjcI1gwilVAAAAAAAAAAAAAAAAAAAA8NEAv0BjMgBAAAA=";
+    const LIST_URL: &str = "https://example.com/credentials/status/3";
+    const SEED: &str = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+
+    fn options(kind: StatusKind, purpose: StatusPurpose) -> CredentialOptions {
+        let key = signing_key(SEED).unwrap();
+        CredentialOptions {
+            id: LIST_URL.to_string(),
+            issuer: did_key(&key.verifying_key()),
+            kind,
+            purpose,
+            issuance_date: Utc.with_ymd_and_hms(2023, 3, 1, 12, 0, 0).unwrap(),
+        }
+    }
+
+    fn status(kind: StatusKind, purpose: StatusPurpose, index: u64) -> Value {
+        match kind {
+            StatusKind::RevocationList2020 => json!({
+                "id": format!("{}#{}", LIST_URL, index),
+                "type": kind.entry_type(),
+                "revocationListIndex": index.to_string(),
+                "revocationListCredential": LIST_URL,
+            }),
+            StatusKind::StatusList2021 => json!({
+                "id": format!("{}#{}", LIST_URL, index),
+                "type": kind.entry_type(),
+                "statusPurpose": purpose.to_string(),
+                "statusListIndex": index.to_string(),
+                "statusListCredential": LIST_URL,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_did_key() {
+        // the test vector of the Ed25519 did:key method
+        let key = signing_key(SEED).unwrap();
+        assert_eq!(
+            did_key(&key.verifying_key()),
+            "did:key:z6MktwupdmLXVVqTzCw4i46r4uGyosGXRnR3XjN4Zq7oMMsw"
+        );
+        assert!(did_key_verification_method(&key.verifying_key())
+            .ends_with("#z6MktwupdmLXVVqTzCw4i46r4uGyosGXRnR3XjN4Zq7oMMsw"));
+
+        let table = ["", "00", "zz", &SEED[..62]];
+        for seed in table {
+            let err = signing_key(seed).unwrap_err();
+            assert_eq!(err.name(), "ERR_INVALID_KEY", "{}", seed);
+        }
+    }
+
+    #[test]
+    fn test_list_issuer() {
+        let key = signing_key(SEED).unwrap().verifying_key();
+        let view = json!({
+            "did": did_key(&key),
+            "verification_method": did_key_verification_method(&key),
+        });
+        let issuer = ListIssuer::from_json(&view).unwrap();
+        assert_eq!(issuer.did, did_key(&key));
+        assert_eq!(issuer.check_key(&key), Ok(()));
+
+        let other_key = SigningKey::from_bytes(&[7; 32]).verifying_key();
+        let err = issuer.check_key(&other_key).unwrap_err();
+        assert_eq!(err.name(), "ERR_ISSUER_MISMATCH");
+        // the verification method must be the one of the signing key and of the issuer DID
+        let table = [
+            did_key_verification_method(&other_key),
+            format!("{}#", did_key(&key)),
+            did_key(&key),
+            "did:near:alice.testnet#key-1".to_string(),
+        ];
+        for verification_method in table {
+            let issuer = ListIssuer::from_json(&json!({
+                "did": did_key(&key),
+                "verification_method": verification_method,
+            }))
+            .unwrap();
+            let err = issuer.check_key(&key).unwrap_err();
+            assert_eq!(err.name(), "ERR_ISSUER_MISMATCH", "{}", verification_method);
+        }
+        // other DID methods cannot be checked offline
+        let issuer = ListIssuer::from_json(&json!({
+            "did": "did:near:alice.testnet",
+            "verification_method": "did:near:alice.testnet#key-1",
+        }))
+        .unwrap();
+        assert_eq!(issuer.check_key(&other_key), Ok(()));
+        let issuer = ListIssuer::from_json(&json!({
+            "did": "did:near:alice.testnet",
+            "verification_method": "did:near:bob.testnet#key-1",
+        }))
+        .unwrap();
+        let err = issuer.check_key(&other_key).unwrap_err();
+        assert_eq!(err.name(), "ERR_ISSUER_MISMATCH");
+
+        let err = ListIssuer::from_json(&json!({"did": "did:near:alice.testnet"})).unwrap_err();
+        assert_eq!(err.name(), "ERR_INVALID_INPUT");
+    }
+
+    #[test]
+    fn test_list_credential() {
+        assert_eq!(
+            read_encoded_list(&format!(" '{}'\n", LIST_WITH_3214_REVOKED)),
+            LIST_WITH_3214_REVOKED
+        );
+        assert_eq!(
+            read_encoded_list(&format!("\"{}\"", LIST_WITH_3214_REVOKED)),
+            LIST_WITH_3214_REVOKED
+        );
+
+        let credential = list_credential(
+            &options(StatusKind::StatusList2021, StatusPurpose::Suspension),
+            LIST_WITH_3214_REVOKED,
+        )
+        .unwrap();
+        assert_eq!(credential["issuanceDate"], "2023-03-01T12:00:00Z");
+        assert_eq!(credential["type"][1], "StatusList2021Credential");
+        assert_eq!(
+            credential["credentialSubject"]["statusPurpose"],
+            "suspension"
+        );
+
+        let credential = list_credential(
+            &options(StatusKind::RevocationList2020, StatusPurpose::Revocation),
+            LIST_WITH_3214_REVOKED,
+        )
+        .unwrap();
+        assert_eq!(credential["type"][1], "RevocationList2020Credential");
+        assert_eq!(credential["credentialSubject"].get("statusPurpose"), None);
+        // the list is GZIP compressed, as required by the specs
+        let encoded_list = credential["credentialSubject"]["encodedList"]
+            .as_str()
+            .unwrap();
+        assert!(encoded_list.starts_with("H4sI"));
+        let rl = rl2020_core::RL2020::from_encoded_list(encoded_list, ()).unwrap();
+        assert!(rl.is_revoked(3214).unwrap());
+        // the bits are ordered as in the specs
+        assert_eq!(
+            rl.decode_bit_set(encoded_list),
+            rl.decode_bit_set(SPEC_LIST_WITH_3214_REVOKED)
+        );
+
+        // the lists smaller than the minimum of the specs are padded
+        let mut rl = rl2020_core::RL2020::new((), 4).unwrap();
+        rl.revoke(3214).unwrap();
+        let credential = list_credential(
+            &options(StatusKind::StatusList2021, StatusPurpose::Revocation),
+            &rl.encoded_list(),
+        )
+        .unwrap();
+        let encoded_list = credential["credentialSubject"]["encodedList"]
+            .as_str()
+            .unwrap();
+        let padded = rl2020_core::RL2020::from_encoded_list(encoded_list, ()).unwrap();
+        assert_eq!(padded.size(), MIN_LIST_SIZE_KB);
+        assert_eq!(
+            padded.decode_bit_set(encoded_list),
+            padded.decode_bit_set(SPEC_LIST_WITH_3214_REVOKED)
+        );
+        let table = [
+            (3214, CredentialState::Revoked),
+            (3209, CredentialState::Valid),
+            (131071, CredentialState::Valid),
+        ];
+        for (index, expected) in table {
+            let got = verify_status(
+                &status(StatusKind::StatusList2021, StatusPurpose::Revocation, index),
+                &credential,
+            );
+            assert_eq!(got, Ok(expected), "index {}", index);
+        }
+
+        let err = list_credential(
+            &options(StatusKind::RevocationList2020, StatusPurpose::Revocation),
+            "not base64!",
+        );
+        assert!(matches!(
+            err,
+            Err(IssueError::InvalidList(RLError::InvalidEncoding(_)))
+        ));
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        // the examples of RFC 8785
+        let value: Value = serde_json::from_str(
+            r#"{
+                "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+                "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+                "literals": [null, true, false]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(proof::canonicalize(&value)).unwrap(),
+            r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#
+        );
+
+        // the names are sorted by their UTF-16 code units
+        let value: Value = serde_json::from_str(
+            r#"{"\u20ac": 1, "\r": 2, "\ufb33": 3, "1": 4, "\ud83d\ude00": 5, "\u0080": 6, "\u00f6": 7}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(proof::canonicalize(&value)).unwrap(),
+            "{\"\\r\":2,\"1\":4,\"\u{80}\":6,\"\u{f6}\":7,\"\u{20ac}\":1,\"\u{1f600}\":5,\"\u{fb33}\":3}"
+        );
+    }
+
+    #[test]
+    fn test_proof_round_trip() {
+        let key = signing_key(SEED).unwrap();
+        let vm = did_key_verification_method(&key.verifying_key());
+        let table = [
+            (
+                StatusKind::RevocationList2020,
+                StatusPurpose::Revocation,
+                CredentialState::Revoked,
+            ),
+            (
+                StatusKind::StatusList2021,
+                StatusPurpose::Revocation,
+                CredentialState::Revoked,
+            ),
+            (
+                StatusKind::StatusList2021,
+                StatusPurpose::Suspension,
+                CredentialState::Suspended,
+            ),
+        ];
+        for (kind, purpose, expected) in table {
+            let credential =
+                list_credential(&options(kind, purpose), LIST_WITH_3214_REVOKED).unwrap();
+            let signed = sign_proof(&credential, &key, &vm, &Utc::now());
+            assert_eq!(signed["proof"]["type"], "DataIntegrityProof");
+            assert_eq!(signed["proof"]["cryptosuite"], "eddsa-jcs-2022");
+            assert_eq!(
+                signed["@context"][2],
+                "https://w3id.org/security/data-integrity/v2"
+            );
+            assert_eq!(signed["proof"]["verificationMethod"], vm.as_str());
+            assert_eq!(verify_proof(&signed, &key.verifying_key()), Ok(()));
+
+            // the signed credential is accepted by the verifier
+            let got = verify_status(&status(kind, purpose, 3214), &signed);
+            assert_eq!(got, Ok(expected), "{:?} {}", kind, purpose);
+            let got = verify_status(&status(kind, purpose, 1), &signed);
+            assert_eq!(got, Ok(CredentialState::Valid), "{:?} {}", kind, purpose);
+        }
+    }
+
+    #[test]
+    fn test_proof_tampering() {
+        let key = signing_key(SEED).unwrap();
+        let vm = did_key_verification_method(&key.verifying_key());
+        let credential = list_credential(
+            &options(StatusKind::StatusList2021, StatusPurpose::Revocation),
+            LIST_WITH_3214_REVOKED,
+        )
+        .unwrap();
+        let signed = sign_proof(&credential, &key, &vm, &Utc::now());
+
+        let mut list_changed = signed.clone();
+        list_changed["credentialSubject"]["encodedList"] =
+            json!("eJztwDEBAAAAwqD1T20MHygAAAAAAAAAAAAAAAAAAADgbUAAAAE=");
+        let mut proof_changed = signed.clone();
+        proof_changed["proof"]["created"] = json!("2020-01-01T00:00:00Z");
+        let mut bad_value = signed.clone();
+        bad_value["proof"]["proofValue"] = json!("uAAAA");
+        let mut no_proof = signed.clone();
+        no_proof.as_object_mut().unwrap().remove("proof");
+        let other_key = SigningKey::from_bytes(&[7; 32]).verifying_key();
+
+        let table = [
+            (list_changed, key.verifying_key()),
+            (proof_changed, key.verifying_key()),
+            (bad_value, key.verifying_key()),
+            (no_proof, key.verifying_key()),
+            (signed, other_key),
+        ];
+        for (credential, key) in table {
+            let err = verify_proof(&credential, &key).unwrap_err();
+            assert_eq!(err.name(), "ERR_INVALID_PROOF", "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_jwt_round_trip() {
+        let key = signing_key(SEED).unwrap();
+        let vm = did_key_verification_method(&key.verifying_key());
+        let credential = list_credential(
+            &options(StatusKind::StatusList2021, StatusPurpose::Suspension),
+            LIST_WITH_3214_REVOKED,
+        )
+        .unwrap();
+        let token = sign_jwt(&credential, &key, &vm).unwrap();
+        assert_eq!(token.split('.').count(), 3);
+
+        let got = verify_jwt(&token, &key.verifying_key()).unwrap();
+        assert_eq!(got, credential);
+        let got = verify_status(
+            &status(StatusKind::StatusList2021, StatusPurpose::Suspension, 3214),
+            &got,
+        );
+        assert_eq!(got, Ok(CredentialState::Suspended));
+
+        let (signing_input, signature) = token.rsplit_once('.').unwrap();
+        let (header, _) = signing_input.split_once('.').unwrap();
+        let other_claims = {
+            use base64::Engine;
+            base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(r#"{"vc":{}}"#)
+        };
+        let table = [
+            format!("{}.{}.{}", header, other_claims, signature),
+            format!("{}.AAAA", signing_input),
+            signing_input.to_string(),
+            "not a token".to_string(),
+        ];
+        for token in table {
+            let err = verify_jwt(&token, &key.verifying_key()).unwrap_err();
+            assert_eq!(err.name(), "ERR_INVALID_JWT", "{}", token);
+        }
+        let other_key = SigningKey::from_bytes(&[7; 32]).verifying_key();
+        let token = sign_jwt(&credential, &key, &vm).unwrap();
+        assert!(verify_jwt(&token, &other_key).is_err());
+    }
+}
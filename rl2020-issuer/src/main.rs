@@ -0,0 +1,148 @@
+//! Command line tool that signs a list credential from an encoded list.
+//!
+//! ```sh
+//! near view rl2020.testnet get_encoded_list '{"id": "alice.testnet:example/rl/1"}' | tail -1 > list.txt
+//! near view rl2020.testnet get_list_issuer '{"id": "alice.testnet:example/rl/1"}' | tail -1 > issuer.json
+//! rl2020-issuer --id https://example.com/status/1 --key-file issuer.key --input list.txt \
+//!     --list-issuer issuer.json
+//! ```
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::process;
+
+use chrono::Utc;
+use clap::{Parser, ValueEnum};
+use rl2020_issuer::{
+    did_key, did_key_verification_method, list_credential, read_encoded_list, sign_jwt, sign_proof,
+    signing_key, CredentialOptions, IssueError, ListIssuer,
+};
+use rl2020_verifier::{StatusKind, StatusPurpose};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Kind {
+    #[value(name = "revocation-list-2020")]
+    RevocationList2020,
+    #[value(name = "status-list-2021")]
+    StatusList2021,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Purpose {
+    Revocation,
+    Suspension,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    /// a JSON credential with a DataIntegrityProof (eddsa-jcs-2022)
+    Proof,
+    /// a VC-JWT
+    Jwt,
+}
+
+/// Wraps an encoded list into a signed RevocationList2020 or StatusList2021 credential
+#[derive(Debug, Parser)]
+struct Args {
+    /// the URL the list credential is served from
+    #[arg(long)]
+    id: String,
+    /// the file with the hex encoded Ed25519 secret key seed
+    #[arg(long)]
+    key_file: PathBuf,
+    /// the file with the output of `get_encoded_list` or a snapshot of the list, stdin if missing
+    #[arg(long)]
+    input: Option<PathBuf>,
+    #[arg(long, value_enum, default_value_t = Kind::StatusList2021)]
+    kind: Kind,
+    /// the purpose of a StatusList2021 list
+    #[arg(long, value_enum, default_value_t = Purpose::Revocation)]
+    purpose: Purpose,
+    /// the file with the issuer recorded for the list (the JSON result of `get_list_issuer`),
+    /// the credential is issued by its DID and signed with its verification method. `null`
+    /// if the list has no recorded issuer
+    #[arg(long)]
+    list_issuer: PathBuf,
+    /// the issuer DID of a list with no recorded issuer, the did:key of the signing key if
+    /// missing
+    #[arg(long)]
+    issuer: Option<String>,
+    /// the verification method of a list with no recorded issuer, the did:key one if missing
+    #[arg(long)]
+    verification_method: Option<String>,
+    #[arg(long, value_enum, default_value_t = Format::Proof)]
+    format: Format,
+}
+
+fn main() {
+    if let Err(e) = run(Args::parse()) {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    let key = signing_key(&fs::read_to_string(&args.key_file)?)?;
+    let input = match &args.input {
+        Some(path) => fs::read_to_string(path)?,
+        None => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            input
+        }
+    };
+
+    let (kind, purpose) = match (args.kind, args.purpose) {
+        (Kind::RevocationList2020, _) => {
+            (StatusKind::RevocationList2020, StatusPurpose::Revocation)
+        }
+        (Kind::StatusList2021, Purpose::Revocation) => {
+            (StatusKind::StatusList2021, StatusPurpose::Revocation)
+        }
+        (Kind::StatusList2021, Purpose::Suspension) => {
+            (StatusKind::StatusList2021, StatusPurpose::Suspension)
+        }
+    };
+    let view: serde_json::Value = serde_json::from_str(&fs::read_to_string(&args.list_issuer)?)
+        .map_err(|e| IssueError::InvalidInput(format!("the list issuer is not JSON: {}", e)))?;
+    let (issuer, verification_method) = match view {
+        serde_json::Value::Null => (
+            args.issuer.unwrap_or_else(|| did_key(&key.verifying_key())),
+            args.verification_method
+                .unwrap_or_else(|| did_key_verification_method(&key.verifying_key())),
+        ),
+        view => {
+            let list_issuer = ListIssuer::from_json(&view)?;
+            list_issuer.check_key(&key.verifying_key())?;
+            if args.issuer.as_ref().is_some_and(|i| *i != list_issuer.did)
+                || args
+                    .verification_method
+                    .as_ref()
+                    .is_some_and(|vm| *vm != list_issuer.verification_method)
+            {
+                return Err(IssueError::IssuerMismatch(
+                    "the issuer must be the one recorded for the list".to_string(),
+                )
+                .into());
+            }
+            (list_issuer.did, list_issuer.verification_method)
+        }
+    };
+    let options = CredentialOptions {
+        id: args.id,
+        issuer,
+        kind,
+        purpose,
+        issuance_date: Utc::now(),
+    };
+
+    let credential = list_credential(&options, read_encoded_list(&input))?;
+    match args.format {
+        Format::Proof => {
+            let signed = sign_proof(&credential, &key, &verification_method, &Utc::now());
+            println!("{}", serde_json::to_string_pretty(&signed)?);
+        }
+        Format::Jwt => println!("{}", sign_jwt(&credential, &key, &verification_method)?),
+    }
+    Ok(())
+}
@@ -0,0 +1,157 @@
+//! `DataIntegrityProof` proofs with the `eddsa-jcs-2022` cryptosuite.
+//!
+//! The signed data is the SHA-256 of the proof options followed by the SHA-256 of the
+//! credential, both canonicalized with the JSON Canonicalization Scheme
+//! ([RFC 8785](https://www.rfc-editor.org/rfc/rfc8785)).
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::credential::format_date;
+use crate::IssueError;
+
+const PROOF_TYPE: &str = "DataIntegrityProof";
+const CRYPTOSUITE: &str = "eddsa-jcs-2022";
+const PROOF_PURPOSE: &str = "assertionMethod";
+/// the context defining the data integrity proofs, not part of the credentials v1 context
+const DATA_INTEGRITY_CONTEXT: &str = "https://w3id.org/security/data-integrity/v2";
+
+/// sign_proof returns the credential with a `DataIntegrityProof` proof, adding the data
+/// integrity context to the credential if missing
+pub fn sign_proof(
+    credential: &Value,
+    key: &SigningKey,
+    verification_method: &str,
+    created: &DateTime<Utc>,
+) -> Value {
+    let mut credential = credential.clone();
+    if let Some(context) = credential["@context"].as_array_mut() {
+        if !context.contains(&json!(DATA_INTEGRITY_CONTEXT)) {
+            context.push(json!(DATA_INTEGRITY_CONTEXT));
+        }
+    }
+    let mut proof = json!({
+        "type": PROOF_TYPE,
+        "cryptosuite": CRYPTOSUITE,
+        "created": format_date(created),
+        "verificationMethod": verification_method,
+        "proofPurpose": PROOF_PURPOSE,
+    });
+    let signature = key.sign(&signing_input(&credential, &proof));
+    proof["proofValue"] = json!(format!(
+        "z{}",
+        bs58::encode(signature.to_bytes()).into_string()
+    ));
+
+    credential["proof"] = proof;
+    credential
+}
+
+/// verify_proof checks the `DataIntegrityProof` proof of a credential
+pub fn verify_proof(credential: &Value, key: &VerifyingKey) -> Result<(), IssueError> {
+    let invalid = |reason: &str| IssueError::InvalidProof(reason.to_string());
+
+    let mut proof = credential
+        .get("proof")
+        .cloned()
+        .ok_or_else(|| invalid("the credential has no proof"))?;
+    if proof.get("type") != Some(&json!(PROOF_TYPE))
+        || proof.get("cryptosuite") != Some(&json!(CRYPTOSUITE))
+    {
+        return Err(invalid(
+            "the proof is not a DataIntegrityProof with the eddsa-jcs-2022 cryptosuite",
+        ));
+    }
+    let proof_value = proof
+        .as_object_mut()
+        .and_then(|p| p.remove("proofValue"))
+        .ok_or_else(|| invalid("the proof has no proofValue"))?;
+    let signature = proof_value
+        .as_str()
+        .and_then(|v| v.strip_prefix('z'))
+        .and_then(|v| bs58::decode(v).into_vec().ok())
+        .and_then(|v| Signature::from_slice(&v).ok())
+        .ok_or_else(|| invalid("the proofValue is not a base58btc multibase signature"))?;
+
+    let mut unsigned = credential.clone();
+    if let Some(c) = unsigned.as_object_mut() {
+        c.remove("proof");
+    }
+    key.verify(&signing_input(&unsigned, &proof), &signature)
+        .map_err(|_| invalid("the signature does not match"))
+}
+
+/// the data signed by the proof, the hash of the proof options followed by the hash of the
+/// credential
+fn signing_input(credential: &Value, proof: &Value) -> Vec<u8> {
+    let mut options = proof.clone();
+    if let Some(context) = credential.get("@context") {
+        options["@context"] = context.clone();
+    }
+    let mut input = Sha256::digest(canonicalize(&options)).to_vec();
+    input.extend_from_slice(&Sha256::digest(canonicalize(credential)));
+    input
+}
+
+/// canonicalize serializes a JSON value with the JSON Canonicalization Scheme: no whitespace,
+/// the object members sorted by the UTF-16 code units of their names and the numbers
+/// serialized as ECMAScript does
+pub(crate) fn canonicalize(value: &Value) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &Value, out: &mut Vec<u8>) {
+    match value {
+        Value::Array(items) => {
+            out.push(b'[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(b']');
+        }
+        Value::Object(members) => {
+            let mut members: Vec<_> = members.iter().collect();
+            members.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            out.push(b'{');
+            for (i, (name, member)) in members.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write_canonical(&json!(name), out);
+                out.push(b':');
+                write_canonical(member, out);
+            }
+            out.push(b'}');
+        }
+        Value::Number(n) => {
+            let n = n.as_f64().expect("a json number is always a finite f64");
+            out.extend_from_slice(ecmascript_number(n).as_bytes());
+        }
+        // the strings are escaped as in JSON.stringify
+        _ => serde_json::to_writer(out, value).expect("a json value can always be serialized"),
+    }
+}
+
+/// ecmascript_number formats a number as the ECMAScript `Number.prototype.toString`,
+/// with the shortest digits that round trip and an exponent outside of [1e-6, 1e21)
+fn ecmascript_number(n: f64) -> String {
+    if n == 0.0 {
+        return "0".to_string();
+    }
+    if (1e-6..1e21).contains(&n.abs()) {
+        return n.to_string();
+    }
+    let formatted = format!("{:e}", n);
+    match formatted.split_once('e') {
+        Some((mantissa, exponent)) if !exponent.starts_with('-') => {
+            format!("{}e+{}", mantissa, exponent)
+        }
+        _ => formatted,
+    }
+}
@@ -0,0 +1,94 @@
+use std::fmt;
+
+use rl2020_core::RLError;
+
+use crate::StatusPurpose;
+
+/// errors returned when verifying a credential status
+///
+/// every variant has a stable name, used as the prefix of the error message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyError {
+    /// the credential status or the list credential type is not supported
+    UnsupportedType(String),
+    /// a required field is missing
+    MissingField(&'static str),
+    /// a field has an unexpected value
+    InvalidField { field: &'static str, reason: String },
+    /// the list credential is not the one referenced by the credential status
+    ListCredentialMismatch { expected: String, got: String },
+    /// the list credential type does not match the credential status type
+    ListTypeMismatch { expected: &'static str, got: String },
+    /// the purpose of the list does not match the purpose of the credential status
+    PurposeMismatch {
+        expected: StatusPurpose,
+        got: StatusPurpose,
+    },
+    /// the index is outside of the list capacity
+    IndexOutOfRange { index: u64, capacity: usize },
+    /// the encoded list could not be decoded
+    CorruptEncoding(RLError),
+    /// the list credential is not issued by the issuer of the credential
+    IssuerMismatch { expected: String, got: String },
+}
+
+impl VerifyError {
+    /// name returns the stable name of the error
+    pub fn name(&self) -> &'static str {
+        match self {
+            VerifyError::UnsupportedType(_) => "ERR_UNSUPPORTED_TYPE",
+            VerifyError::MissingField(_) => "ERR_MISSING_FIELD",
+            VerifyError::InvalidField { .. } => "ERR_INVALID_FIELD",
+            VerifyError::ListCredentialMismatch { .. } => "ERR_LIST_CREDENTIAL_MISMATCH",
+            VerifyError::ListTypeMismatch { .. } => "ERR_LIST_TYPE_MISMATCH",
+            VerifyError::PurposeMismatch { .. } => "ERR_PURPOSE_MISMATCH",
+            VerifyError::IndexOutOfRange { .. } => "ERR_INDEX_OUT_OF_RANGE",
+            VerifyError::CorruptEncoding(_) => "ERR_CORRUPT_ENCODING",
+            VerifyError::IssuerMismatch { .. } => "ERR_ISSUER_MISMATCH",
+        }
+    }
+}
+
+/// the error is displayed as `<name>: <details>`
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.name())?;
+        match self {
+            VerifyError::UnsupportedType(t) => write!(f, "{}", t),
+            VerifyError::MissingField(field) => write!(f, "{}", field),
+            VerifyError::InvalidField { field, reason } => write!(f, "{} {}", field, reason),
+            VerifyError::ListCredentialMismatch { expected, got } => {
+                write!(f, "expected {}, got {}", expected, got)
+            }
+            VerifyError::ListTypeMismatch { expected, got } => {
+                write!(f, "expected {}, got {}", expected, got)
+            }
+            VerifyError::PurposeMismatch { expected, got } => {
+                write!(f, "expected {}, got {}", expected, got)
+            }
+            VerifyError::IssuerMismatch { expected, got } => {
+                write!(f, "expected {}, got {}", expected, got)
+            }
+            VerifyError::IndexOutOfRange { index, capacity } => write!(
+                f,
+                "max indexable element is {}, provided index {} is out of range",
+                capacity.saturating_sub(1),
+                index
+            ),
+            VerifyError::CorruptEncoding(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+impl From<RLError> for VerifyError {
+    fn from(e: RLError) -> Self {
+        match e {
+            RLError::OutOfBounds { index, capacity } => {
+                VerifyError::IndexOutOfRange { index, capacity }
+            }
+            e => VerifyError::CorruptEncoding(e),
+        }
+    }
+}
@@ -0,0 +1,336 @@
+//! Off-chain verification of the `credentialStatus` entries of verifiable credentials against
+//! [RevocationList2020](https://w3c-ccg.github.io/vc-status-rl-2020/) and
+//! [StatusList2021](https://w3c.github.io/vc-status-list-2021/) list credentials.
+//!
+//! The encoded list of the list credential is either GZIP compressed, as required by the specs,
+//! or zlib compressed as returned by the `get_encoded_list` method of the NEAR and Casper
+//! contracts, and is decoded with [`rl2020_core`]. The index 0 of a GZIP list is the left-most
+//! (most significant) bit of the first byte as defined by the specs, the zlib lists keep the
+//! bit order of the contracts.
+mod error;
+mod status;
+
+use rl2020_core::RL2020;
+use serde_json::Value;
+
+pub use error::VerifyError;
+pub use status::{ListCredential, StatusEntry, StatusKind, StatusPurpose};
+
+/// the status of a credential
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CredentialState {
+    Valid,
+    Revoked,
+    Suspended,
+}
+
+/// verify_status returns the state of a credential given its `credentialStatus` entry
+/// and the list credential it references
+pub fn verify_status(
+    status: &Value,
+    list_credential: &Value,
+) -> Result<CredentialState, VerifyError> {
+    let entry = StatusEntry::from_json(status)?;
+    let list = ListCredential::from_json(list_credential)?;
+    check(&entry, &list)
+}
+
+/// check_issuer verifies that the list credential is issued by the issuer of the credential,
+/// the issuer is either a DID or an object with the DID as `id`
+pub fn check_issuer(credential: &Value, list_credential: &Value) -> Result<(), VerifyError> {
+    let expected = issuer(credential)?;
+    let got = issuer(list_credential)?;
+    if expected != got {
+        return Err(VerifyError::IssuerMismatch {
+            expected: expected.to_string(),
+            got: got.to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn issuer(credential: &Value) -> Result<&str, VerifyError> {
+    let issuer = credential
+        .get("issuer")
+        .ok_or(VerifyError::MissingField("issuer"))?;
+    issuer
+        .as_str()
+        .or_else(|| issuer.get("id").and_then(Value::as_str))
+        .ok_or_else(|| VerifyError::InvalidField {
+            field: "issuer",
+            reason: "is not a DID or an object with an id".to_string(),
+        })
+}
+
+/// check returns the state of a credential looking up its entry in the list credential
+pub fn check(entry: &StatusEntry, list: &ListCredential) -> Result<CredentialState, VerifyError> {
+    if entry.list_credential != list.id {
+        return Err(VerifyError::ListCredentialMismatch {
+            expected: entry.list_credential.clone(),
+            got: list.id.clone(),
+        });
+    }
+    if entry.kind != list.kind {
+        return Err(VerifyError::ListTypeMismatch {
+            expected: entry.kind.credential_type(),
+            got: list.kind.credential_type().to_string(),
+        });
+    }
+    if entry.purpose != list.purpose {
+        return Err(VerifyError::PurposeMismatch {
+            expected: entry.purpose,
+            got: list.purpose,
+        });
+    }
+    let rl = RL2020::from_encoded_list(&list.encoded_list, ())?;
+    if !rl.get(entry.index)? {
+        return Ok(CredentialState::Valid);
+    }
+    Ok(match entry.purpose {
+        StatusPurpose::Revocation => CredentialState::Revoked,
+        StatusPurpose::Suspension => CredentialState::Suspended,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rl2020_core::RLError;
+    use serde_json::json;
+
+    // the encoded lists for a 16kb list, as served by the contracts
+    const EMPTY_LIST: &str = "eJztwDEBAAAAwqD1T20MHygAAAAAAAAAAAAAAAAAAADgbUAAAAE=";
+    const LIST_WITH_3214_REVOKED: &str = "eJztwAENAAAIwKBHs38qa7gJxSkTAAAAAAAAAAAAAAAAAAAADy3coQBB";
+    // the same list GZIP compressed with the bit order of the specs, by Python's gzip module
+    const GZIP_LIST_WITH_3214_REVOKED: &str =
+        "H4sIAAAAAAACA+3QAQkAAAwDoLH+oVfjcI1gwilVAAAAAAAAAAAAAAAAAAAA8NEAv0BjMgBAAAA=";
+    const LIST_URL: &str = "https://example.com/credentials/status/3";
+
+    fn rl2020_status(index: Value) -> Value {
+        json!({
+            "id": format!("{}#{}", LIST_URL, index),
+            "type": "RevocationList2020Status",
+            "revocationListIndex": index,
+            "revocationListCredential": LIST_URL,
+        })
+    }
+
+    fn rl2020_credential(encoded_list: &str) -> Value {
+        json!({
+            "@context": ["https://www.w3.org/2018/credentials/v1", "https://w3id.org/vc-revocation-list-2020/v1"],
+            "id": LIST_URL,
+            "type": ["VerifiableCredential", "RevocationList2020Credential"],
+            "issuer": "did:example:12345",
+            "credentialSubject": {
+                "id": format!("{}#list", LIST_URL),
+                "type": "RevocationList2020",
+                "encodedList": encoded_list,
+            },
+        })
+    }
+
+    fn sl2021_status(index: u64, purpose: &str) -> Value {
+        json!({
+            "id": format!("{}#{}", LIST_URL, index),
+            "type": "StatusList2021Entry",
+            "statusPurpose": purpose,
+            "statusListIndex": index.to_string(),
+            "statusListCredential": LIST_URL,
+        })
+    }
+
+    fn sl2021_credential(encoded_list: &str, purpose: &str) -> Value {
+        json!({
+            "@context": ["https://www.w3.org/2018/credentials/v1", "https://w3id.org/vc/status-list/2021/v1"],
+            "id": LIST_URL,
+            "type": ["VerifiableCredential", "StatusList2021Credential"],
+            "issuer": "did:example:12345",
+            "credentialSubject": {
+                "id": format!("{}#list", LIST_URL),
+                "type": "StatusList2021",
+                "statusPurpose": purpose,
+                "encodedList": encoded_list,
+            },
+        })
+    }
+
+    #[test]
+    fn test_revocation_list_2020() {
+        let credential = rl2020_credential(LIST_WITH_3214_REVOKED);
+        // the index is a string in the spec, numbers are accepted too
+        let table = [
+            (json!("3214"), CredentialState::Revoked),
+            (json!(3214), CredentialState::Revoked),
+            (json!("3213"), CredentialState::Valid),
+            (json!(0), CredentialState::Valid),
+        ];
+        for (index, expected) in table {
+            let got = verify_status(&rl2020_status(index.clone()), &credential);
+            assert_eq!(got, Ok(expected), "index {}", index);
+        }
+        let got = verify_status(&rl2020_status(json!(3214)), &rl2020_credential(EMPTY_LIST));
+        assert_eq!(got, Ok(CredentialState::Valid));
+    }
+
+    #[test]
+    fn test_status_list_2021() {
+        let table = [
+            (
+                "revocation",
+                LIST_WITH_3214_REVOKED,
+                CredentialState::Revoked,
+            ),
+            (
+                "suspension",
+                LIST_WITH_3214_REVOKED,
+                CredentialState::Suspended,
+            ),
+            ("revocation", EMPTY_LIST, CredentialState::Valid),
+            ("suspension", EMPTY_LIST, CredentialState::Valid),
+            (
+                "revocation",
+                GZIP_LIST_WITH_3214_REVOKED,
+                CredentialState::Revoked,
+            ),
+        ];
+        for (purpose, encoded_list, expected) in table {
+            let got = verify_status(
+                &sl2021_status(3214, purpose),
+                &sl2021_credential(encoded_list, purpose),
+            );
+            assert_eq!(got, Ok(expected), "{} {}", purpose, encoded_list);
+        }
+    }
+
+    #[test]
+    fn test_spec_vectors() {
+        // the list of the StatusList2021 spec example
+        let credential = sl2021_credential(
+            "H4sIAAAAAAAAA-3BMQEAAADCoPVPbQwfoAAAAAAAAAAAAAAAAAAAAIC3AYbSVKsAQAAA",
+            "revocation",
+        );
+        for index in [0, 3214, 131071] {
+            let got = verify_status(&sl2021_status(index, "revocation"), &credential);
+            assert_eq!(got, Ok(CredentialState::Valid), "index {}", index);
+        }
+
+        // the index 3214 is the bit 0x02 of the byte 401, an issuer setting the bit 0x40
+        // would revoke the index 3209
+        let credential = sl2021_credential(GZIP_LIST_WITH_3214_REVOKED, "revocation");
+        let table = [
+            (3214, CredentialState::Revoked),
+            (3209, CredentialState::Valid),
+            (3213, CredentialState::Valid),
+            (3215, CredentialState::Valid),
+        ];
+        for (index, expected) in table {
+            let got = verify_status(&sl2021_status(index, "revocation"), &credential);
+            assert_eq!(got, Ok(expected), "index {}", index);
+        }
+    }
+
+    #[test]
+    fn test_check_issuer() {
+        let list = rl2020_credential(EMPTY_LIST);
+        let table = [
+            (json!({"issuer": "did:example:12345"}), None),
+            (
+                json!({"issuer": {"id": "did:example:12345", "name": "Example"}}),
+                None,
+            ),
+            (
+                json!({"issuer": "did:example:67890"}),
+                Some("ERR_ISSUER_MISMATCH"),
+            ),
+            (json!({"issuer": 12345}), Some("ERR_INVALID_FIELD")),
+            (json!({}), Some("ERR_MISSING_FIELD")),
+        ];
+        for (credential, expected) in table {
+            let got = check_issuer(&credential, &list).err();
+            assert_eq!(
+                got.as_ref().map(VerifyError::name),
+                expected,
+                "{}",
+                credential
+            );
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut wrong_url = rl2020_credential(EMPTY_LIST);
+        wrong_url["id"] = json!("https://example.com/credentials/status/4");
+        let mut corrupt = rl2020_credential(EMPTY_LIST);
+        corrupt["credentialSubject"]["encodedList"] = json!("eJztwDEBAAAAwqD1T20MHygAAA");
+        let mut unsupported = rl2020_status(json!(1));
+        unsupported["type"] = json!("BitstringStatusListEntry");
+        let mut missing = rl2020_status(json!(1));
+        missing
+            .as_object_mut()
+            .unwrap()
+            .remove("revocationListCredential");
+
+        let table = [
+            (
+                rl2020_status(json!(131072)),
+                rl2020_credential(EMPTY_LIST),
+                "ERR_INDEX_OUT_OF_RANGE",
+            ),
+            (
+                rl2020_status(json!("-1")),
+                rl2020_credential(EMPTY_LIST),
+                "ERR_INVALID_FIELD",
+            ),
+            (
+                rl2020_status(json!(1)),
+                wrong_url,
+                "ERR_LIST_CREDENTIAL_MISMATCH",
+            ),
+            (
+                rl2020_status(json!(1)),
+                rl2020_credential("not base64!"),
+                "ERR_CORRUPT_ENCODING",
+            ),
+            (rl2020_status(json!(1)), corrupt, "ERR_CORRUPT_ENCODING"),
+            (
+                unsupported,
+                rl2020_credential(EMPTY_LIST),
+                "ERR_UNSUPPORTED_TYPE",
+            ),
+            (missing, rl2020_credential(EMPTY_LIST), "ERR_MISSING_FIELD"),
+            (
+                rl2020_status(json!(1)),
+                sl2021_credential(EMPTY_LIST, "revocation"),
+                "ERR_LIST_TYPE_MISMATCH",
+            ),
+            (
+                sl2021_status(1, "suspension"),
+                sl2021_credential(EMPTY_LIST, "revocation"),
+                "ERR_PURPOSE_MISMATCH",
+            ),
+            (
+                sl2021_status(1, "refresh"),
+                sl2021_credential(EMPTY_LIST, "revocation"),
+                "ERR_INVALID_FIELD",
+            ),
+        ];
+        for (status, credential, expected) in table {
+            let err = verify_status(&status, &credential).unwrap_err();
+            assert_eq!(err.name(), expected, "{}", err);
+        }
+
+        let err = verify_status(
+            &rl2020_status(json!(131072)),
+            &rl2020_credential(EMPTY_LIST),
+        );
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "ERR_INDEX_OUT_OF_RANGE: max indexable element is 131071, provided index 131072 is out of range"
+        );
+        let err = verify_status(&rl2020_status(json!(1)), &rl2020_credential("not base64!"));
+        assert!(matches!(
+            err,
+            Err(VerifyError::CorruptEncoding(RLError::InvalidEncoding(_)))
+        ));
+    }
+}
@@ -0,0 +1,197 @@
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::VerifyError;
+
+/// the status list flavours supported by the verifier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusKind {
+    /// https://w3c-ccg.github.io/vc-status-rl-2020/
+    RevocationList2020,
+    /// https://w3c.github.io/vc-status-list-2021/
+    StatusList2021,
+}
+
+impl StatusKind {
+    /// the type of the `credentialStatus` entry
+    pub fn entry_type(&self) -> &'static str {
+        match self {
+            StatusKind::RevocationList2020 => "RevocationList2020Status",
+            StatusKind::StatusList2021 => "StatusList2021Entry",
+        }
+    }
+
+    /// the type of the list credential
+    pub fn credential_type(&self) -> &'static str {
+        match self {
+            StatusKind::RevocationList2020 => "RevocationList2020Credential",
+            StatusKind::StatusList2021 => "StatusList2021Credential",
+        }
+    }
+
+    /// the type of the list credential subject
+    pub fn subject_type(&self) -> &'static str {
+        match self {
+            StatusKind::RevocationList2020 => "RevocationList2020",
+            StatusKind::StatusList2021 => "StatusList2021",
+        }
+    }
+
+    fn index_field(&self) -> &'static str {
+        match self {
+            StatusKind::RevocationList2020 => "revocationListIndex",
+            StatusKind::StatusList2021 => "statusListIndex",
+        }
+    }
+
+    fn credential_field(&self) -> &'static str {
+        match self {
+            StatusKind::RevocationList2020 => "revocationListCredential",
+            StatusKind::StatusList2021 => "statusListCredential",
+        }
+    }
+}
+
+/// the purpose of a status list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusPurpose {
+    Revocation,
+    Suspension,
+}
+
+impl StatusPurpose {
+    fn parse(purpose: &str) -> Result<Self, VerifyError> {
+        match purpose {
+            "revocation" => Ok(StatusPurpose::Revocation),
+            "suspension" => Ok(StatusPurpose::Suspension),
+            other => Err(VerifyError::InvalidField {
+                field: "statusPurpose",
+                reason: format!("is not supported: {}", other),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for StatusPurpose {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatusPurpose::Revocation => write!(f, "revocation"),
+            StatusPurpose::Suspension => write!(f, "suspension"),
+        }
+    }
+}
+
+/// the `credentialStatus` entry of a verifiable credential
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusEntry {
+    pub kind: StatusKind,
+    pub purpose: StatusPurpose,
+    /// the position of the credential in the list
+    pub index: u64,
+    /// the URL of the list credential
+    pub list_credential: String,
+}
+
+impl StatusEntry {
+    /// parses a `RevocationList2020Status` or a `StatusList2021Entry` entry
+    pub fn from_json(status: &Value) -> Result<Self, VerifyError> {
+        let kind = match str_field(status, "type")? {
+            "RevocationList2020Status" => StatusKind::RevocationList2020,
+            "StatusList2021Entry" => StatusKind::StatusList2021,
+            other => return Err(VerifyError::UnsupportedType(other.to_string())),
+        };
+        // the RevocationList2020 lists are revocation lists only
+        let purpose = match kind {
+            StatusKind::RevocationList2020 => StatusPurpose::Revocation,
+            StatusKind::StatusList2021 => {
+                StatusPurpose::parse(str_field(status, "statusPurpose")?)?
+            }
+        };
+        Ok(StatusEntry {
+            kind,
+            purpose,
+            index: index_field(status, kind.index_field())?,
+            list_credential: str_field(status, kind.credential_field())?.to_string(),
+        })
+    }
+}
+
+/// the list credential referenced by a `credentialStatus` entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListCredential {
+    pub id: String,
+    pub kind: StatusKind,
+    pub purpose: StatusPurpose,
+    /// the list compressed and base64 encoded, as returned by `get_encoded_list`
+    pub encoded_list: String,
+}
+
+impl ListCredential {
+    /// parses a `RevocationList2020Credential` or a `StatusList2021Credential`
+    pub fn from_json(credential: &Value) -> Result<Self, VerifyError> {
+        let subject = credential
+            .get("credentialSubject")
+            .ok_or(VerifyError::MissingField("credentialSubject"))?;
+        let kind = match str_field(subject, "type")? {
+            "RevocationList2020" => StatusKind::RevocationList2020,
+            "StatusList2021" => StatusKind::StatusList2021,
+            other => return Err(VerifyError::UnsupportedType(other.to_string())),
+        };
+        if !has_type(credential, kind.credential_type()) {
+            return Err(VerifyError::ListTypeMismatch {
+                expected: kind.credential_type(),
+                got: credential
+                    .get("type")
+                    .map(Value::to_string)
+                    .unwrap_or_default(),
+            });
+        }
+        let purpose = match kind {
+            StatusKind::RevocationList2020 => StatusPurpose::Revocation,
+            StatusKind::StatusList2021 => {
+                StatusPurpose::parse(str_field(subject, "statusPurpose")?)?
+            }
+        };
+        Ok(ListCredential {
+            id: str_field(credential, "id")?.to_string(),
+            kind,
+            purpose,
+            encoded_list: str_field(subject, "encodedList")?.to_string(),
+        })
+    }
+}
+
+/// returns a string field of a json object
+fn str_field<'a>(value: &'a Value, field: &'static str) -> Result<&'a str, VerifyError> {
+    value
+        .get(field)
+        .ok_or(VerifyError::MissingField(field))?
+        .as_str()
+        .ok_or_else(|| VerifyError::InvalidField {
+            field,
+            reason: "is not a string".to_string(),
+        })
+}
+
+/// returns an index field, the specs encode the index as a string but numbers are accepted too
+fn index_field(value: &Value, field: &'static str) -> Result<u64, VerifyError> {
+    let invalid = || VerifyError::InvalidField {
+        field,
+        reason: "is not a positive integer".to_string(),
+    };
+    match value.get(field).ok_or(VerifyError::MissingField(field))? {
+        Value::String(s) => s.parse().map_err(|_| invalid()),
+        Value::Number(n) => n.as_u64().ok_or_else(invalid),
+        _ => Err(invalid()),
+    }
+}
+
+/// returns true if the `type` of the credential is or contains the given type
+fn has_type(credential: &Value, credential_type: &str) -> bool {
+    match credential.get("type") {
+        Some(Value::String(t)) => t == credential_type,
+        Some(Value::Array(types)) => types.iter().any(|t| t == credential_type),
+        _ => false,
+    }
+}